@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+
+/// Archive formats that can be unpacked without external tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    TarGz,
+    TarXz,
+    TarBz2,
+    Zip,
+}
+
+impl ArchiveKind {
+    /// Detect the archive format from an asset file name
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar.xz") {
+            Some(Self::TarXz)
+        } else if name.ends_with(".tar.bz2") {
+            Some(Self::TarBz2)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Unpack `archive` into `dest`, refusing any entry that would land outside it
+pub fn extract(archive: &Path, kind: ArchiveKind, dest: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest)?;
+
+    let file = BufReader::new(
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?,
+    );
+
+    match kind {
+        ArchiveKind::TarGz => extract_tar(flate2::read::GzDecoder::new(file), dest),
+        ArchiveKind::TarXz => extract_tar(xz2::read::XzDecoder::new(file), dest),
+        ArchiveKind::TarBz2 => extract_tar(bzip2::read::BzDecoder::new(file), dest),
+        ArchiveKind::Zip => extract_zip(archive, dest),
+    }
+}
+
+fn extract_tar(reader: impl Read, dest: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+
+    for entry in archive.entries().context("Failed to read tar archive")? {
+        let mut entry = entry.context("Failed to read tar entry")?;
+        let path = entry.path()?.into_owned();
+        ensure_contained(&path)?;
+
+        // Links are never install candidates, and skipping them means nothing
+        // written later can be redirected outside `dest`
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            continue;
+        }
+
+        entry
+            .unpack_in(dest)
+            .with_context(|| format!("Failed to extract {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn extract_zip(archive: &Path, dest: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut zip =
+        zip::ZipArchive::new(File::open(archive)?).context("Failed to read zip archive")?;
+
+    for index in 0..zip.len() {
+        let mut file = zip.by_index(index)?;
+        let path = file.enclosed_name().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Refusing to extract {}: path escapes the archive",
+                file.name()
+            )
+        })?;
+        let target = dest.join(&path);
+
+        if file.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            continue;
+        }
+        if file.is_symlink() {
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out = File::create(&target)
+            .with_context(|| format!("Failed to extract {}", path.display()))?;
+        std::io::copy(&mut file, &mut out)?;
+
+        if let Some(mode) = file.unix_mode() {
+            std::fs::set_permissions(&target, std::fs::Permissions::from_mode(mode & 0o777))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reject absolute paths and `..` components
+fn ensure_contained(path: &Path) -> Result<()> {
+    let escapes = path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+
+    if escapes {
+        anyhow::bail!(
+            "Refusing to extract {}: path escapes the archive",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Find every executable under `root`, returned relative to it
+///
+/// Native binaries are recognised by their ELF or Mach-O header, whatever
+/// their mode. Files with the exec bit set count only when they start with a
+/// `#!` line, since archives often mark documentation executable too.
+pub fn find_executables(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    walk(root, root, &mut found)?;
+    found.sort();
+    Ok(found)
+}
+
+fn walk(root: &Path, dir: &Path, found: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            walk(root, &path, found)?;
+        } else if file_type.is_file() && is_executable(&path)? {
+            found.push(path.strip_prefix(root)?.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+/// Whether a file is a native binary or an executable script
+pub fn is_executable(path: &Path) -> Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut header = [0u8; 4];
+    let read = File::open(path)?.read(&mut header)?;
+    let header = &header[..read];
+
+    if is_native_binary(header) {
+        return Ok(true);
+    }
+
+    let exec_bit = std::fs::metadata(path)?.permissions().mode() & 0o111 != 0;
+    Ok(exec_bit && header.starts_with(b"#!"))
+}
+
+fn is_native_binary(header: &[u8]) -> bool {
+    const MAGICS: [[u8; 4]; 6] = [
+        [0x7f, b'E', b'L', b'F'], // ELF
+        [0xfe, 0xed, 0xfa, 0xce], // Mach-O 32-bit
+        [0xce, 0xfa, 0xed, 0xfe], // Mach-O 32-bit, little endian
+        [0xfe, 0xed, 0xfa, 0xcf], // Mach-O 64-bit
+        [0xcf, 0xfa, 0xed, 0xfe], // Mach-O 64-bit, little endian
+        [0xca, 0xfe, 0xba, 0xbe], // Mach-O universal
+    ];
+
+    MAGICS.iter().any(|magic| header == magic)
+}
@@ -1,14 +1,18 @@
 use crate::command::{CommandRunner, SystemCommandRunner};
+use crate::facts::{Download, Provenance};
+use crate::github::GitHubClient;
 use crate::knowledge::{Installer, Tool, ToolInstaller};
+use crate::lock::LockedTool;
+use crate::output::outln;
 use crate::platform::Platform;
 use anyhow::Result;
 use regex::Regex;
-use std::path::PathBuf;
-use std::process::Command;
+use std::path::Path;
 
 pub struct InstallResult {
     pub version: String,
     pub executables: Option<Vec<String>>,
+    pub provenance: Provenance,
 }
 
 pub fn execute_install(
@@ -36,22 +40,86 @@ pub fn execute_install_with_runner(
     platform: &Platform,
     runner: &dyn CommandRunner,
 ) -> Result<InstallResult> {
-    let mut command = installer.install.clone();
+    let command = expand_command(
+        installer.install_template(version)?,
+        tool_name,
+        tool_config,
+        version,
+        platform,
+    );
+
+    outln!("🔨 Running: {}", command.join(" "));
 
-    // Expand templates
-    for part in &mut command {
-        *part = expand_template(part, tool_name, tool_config, version, platform);
+    let output = runner.run(&command[0], &command[1..])?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Command failed: {}", stderr);
     }
 
-    println!("🔨 Running: {}", command.join(" "));
+    let version = version_from_output(
+        installer,
+        tool_name,
+        tool_config,
+        version,
+        platform,
+        &output,
+    )?;
+
+    Ok(InstallResult {
+        version,
+        executables: None,
+        provenance: Provenance {
+            command: Some(command),
+            ..Default::default()
+        },
+    })
+}
+
+/// Upgrade a tool in place with its installer's `update` command
+pub fn execute_update_with_runner(
+    installer: &Installer,
+    tool_name: &str,
+    tool_config: &ToolInstaller,
+    platform: &Platform,
+    runner: &dyn CommandRunner,
+) -> Result<InstallResult> {
+    let template = installer
+        .update
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("No update command defined for this installer"))?;
+    let command = expand_command(template, tool_name, tool_config, None, platform);
+
+    outln!("🔨 Running: {}", command.join(" "));
 
     let output = runner.run(&command[0], &command[1..])?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Command failed: {}", stderr);
+        anyhow::bail!("Update command failed: {}", stderr);
     }
 
+    let version = version_from_output(installer, tool_name, tool_config, None, platform, &output)?;
+
+    Ok(InstallResult {
+        version,
+        executables: None,
+        provenance: Provenance {
+            command: Some(command),
+            ..Default::default()
+        },
+    })
+}
+
+/// Read the installed version out of an install or update command's output
+fn version_from_output(
+    installer: &Installer,
+    tool_name: &str,
+    tool_config: &ToolInstaller,
+    version: Option<&str>,
+    platform: &Platform,
+    output: &std::process::Output,
+) -> Result<String> {
     // Extract version from output - REQUIRED
     let pattern_template = installer
         .install_output_pattern
@@ -66,21 +134,53 @@ pub fn execute_install_with_runner(
     let combined = format!("{}\n{}", stdout, stderr);
 
     // Check combined output
-    let version = extract_with_pattern(&combined, &pattern)
-        .ok_or_else(|| {
-            if std::env::var("FORGE_DEBUG").is_ok() {
-                eprintln!("DEBUG: Pattern: {}", pattern);
-                eprintln!("DEBUG: Output:\n{}", combined);
-            }
-            anyhow::anyhow!(
-                "Failed to extract version from install output.\nPattern: {}\nHint: Run with FORGE_DEBUG=1 to see full output", 
-                pattern
-            )
-        })?;
+    extract_with_pattern(&combined, &pattern).ok_or_else(|| {
+        if std::env::var("FORGE_DEBUG").is_ok() {
+            eprintln!("DEBUG: Pattern: {}", pattern);
+            eprintln!("DEBUG: Output:\n{}", combined);
+        }
+        anyhow::anyhow!(
+            "Failed to extract version from install output.\nPattern: {}\nHint: Run with FORGE_DEBUG=1 to see full output",
+            pattern
+        )
+    })
+}
+
+/// Run a tool's platform update script and read back the version it left
+pub fn execute_script_update(
+    script: &str,
+    tool_name: &str,
+    platform: &Platform,
+    tool: &Tool,
+    bin_dir: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<InstallResult> {
+    let expanded_script = platform.expand_pattern(script);
+
+    outln!("🔨 Running update script...");
+
+    let script_sha256 = crate::checksum::sha256_bytes(expanded_script.as_bytes());
+    let output = runner.run("sh", &["-c".to_string(), expanded_script])?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Update script failed: {}", stderr);
+    }
+
+    let version = detect_tool_version(tool_name, tool, bin_dir, runner)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Could not detect version for {} after its update script ran",
+            tool_name
+        )
+    })?;
 
     Ok(InstallResult {
         version,
-        executables: None,
+        executables: Some(tool.provides.clone()),
+        provenance: Provenance {
+            script_sha256: Some(script_sha256),
+            ..Default::default()
+        },
     })
 }
 
@@ -90,19 +190,20 @@ pub fn execute_script_install(
     platform: &Platform,
     tool: &Tool,
     tool_installer: &ToolInstaller,
+    bin_dir: &Path,
+    runner: &dyn CommandRunner,
 ) -> Result<InstallResult> {
     let expanded_script = platform.expand_pattern(script);
 
-    println!("🔍 Running the following script:");
-    println!("{}", crate::color::Colors::muted(&expanded_script));
+    outln!("🔍 Running the following script:");
+    outln!("{}", crate::color::Colors::muted(&expanded_script));
+
+    outln!("🔨 Running installer script...");
 
-    println!("🔨 Running installer script...");
+    let script_sha256 = crate::checksum::sha256_bytes(expanded_script.as_bytes());
 
     // Execute via sh -c
-    let output = Command::new("sh")
-        .arg("-c")
-        .arg(&expanded_script)
-        .output()?;
+    let output = runner.run("sh", &["-c".to_string(), expanded_script])?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -110,35 +211,32 @@ pub fn execute_script_install(
     }
 
     // Detect version post-install
-    let version = detect_tool_version(tool_name, tool)?;
+    let version = detect_tool_version(tool_name, tool, bin_dir, runner)?;
 
     // If no version detected, attempt rollback
     if version.is_none() {
-        println!(
+        outln!(
             "❌ Could not detect version for {}. Attempting rollback...",
             tool_name
         );
 
         // Try to run uninstall script if available
-        if let Some(platform_scripts) = get_platform_scripts(tool_installer, platform) {
-            if let Some(uninstall_script) = &platform_scripts.uninstall {
-                println!("  Running uninstall script...");
-                let _ = Command::new("sh")
-                    .arg("-c")
-                    .arg(platform.expand_pattern(uninstall_script))
-                    .output();
-            }
+        if let Some(platform_scripts) = get_platform_scripts(tool_installer, platform)
+            && let Some(uninstall_script) = &platform_scripts.uninstall
+        {
+            outln!("  Running uninstall script...");
+            let _ = runner.run(
+                "sh",
+                &["-c".to_string(), platform.expand_pattern(uninstall_script)],
+            );
         }
 
         // Also try to remove from ~/.local/bin if we know what was installed
         if !tool.provides.is_empty() {
             for exe in &tool.provides {
-                let exe_path = dirs::home_dir()
-                    .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-                    .join(".local/bin")
-                    .join(exe);
+                let exe_path = bin_dir.join(exe);
                 if exe_path.exists() {
-                    println!("  Removing {}", exe_path.display());
+                    outln!("  Removing {}", exe_path.display());
                     std::fs::remove_file(&exe_path).ok();
                 }
             }
@@ -154,6 +252,10 @@ pub fn execute_script_install(
     Ok(InstallResult {
         version: version.unwrap(),
         executables: Some(tool.provides.clone()),
+        provenance: Provenance {
+            script_sha256: Some(script_sha256),
+            ..Default::default()
+        },
     })
 }
 
@@ -162,70 +264,140 @@ pub fn execute_github_install(
     tool_config: &ToolInstaller,
     tool: &Tool,
     platform: &Platform,
+    version: Option<&str>,
+    bin_dir: &Path,
+    github: &GitHubClient,
 ) -> Result<InstallResult> {
-    use crate::github::{discover_asset, download_and_install};
+    use crate::github::{discover_asset, download_and_install, download_matching};
 
     let repo = tool_config
         .repo
         .as_ref()
         .ok_or_else(|| anyhow::anyhow!("GitHub installer requires 'repo' field"))?;
 
-    // If pattern is provided, use the old behavior
+    // If pattern is provided, download the matching assets as-is
     if let Some(pattern) = &tool_config.pattern {
-        // Use existing gh CLI approach
         let expanded_pattern = platform.expand_pattern(pattern);
-
-        let output = Command::new("gh")
-            .args(&[
-                "release",
-                "download",
-                "--repo",
-                repo,
-                "--pattern",
-                &expanded_pattern,
-                "--skip-existing",
-                "--dir",
-                "~/.local/bin",
-            ])
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("GitHub download failed: {}", stderr);
-        }
-
-        // Extract version from output if possible
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let version = extract_version(&stdout).unwrap_or_else(|| "unknown".to_string());
+        let result = download_matching(
+            repo,
+            &expanded_pattern,
+            version,
+            tool_config.sha256.as_deref(),
+            bin_dir,
+            github,
+        )?;
 
         return Ok(InstallResult {
-            version,
-            executables: None,
+            version: result.version,
+            executables: Some(result.executables),
+            provenance: Provenance {
+                tag: Some(result.tag),
+                downloads: result.downloads,
+                ..Default::default()
+            },
         });
     }
 
     // Smart discovery path
-    let discovery = discover_asset(repo, &platform.os, &platform.arch)?;
+    let discovery = discover_asset(repo, &platform.os, &platform.arch, version, github)?;
 
     // Get provides hint from tool definition
     let provides_hint = &tool.provides;
 
     // Download and install
     let install_result = download_and_install(
-        &discovery.download_url,
-        &discovery.asset_name,
+        &discovery,
         tool_name,
         provides_hint,
+        tool_config.sha256.as_deref(),
+        bin_dir,
+        github,
     )?;
 
     // Print what we installed
     for exe in &install_result.executables {
-        println!("  Installed: {}", exe);
+        outln!("  Installed: {}", exe);
     }
 
     Ok(InstallResult {
         version: discovery.version,
         executables: Some(install_result.executables),
+        provenance: Provenance {
+            tag: Some(discovery.tag),
+            downloads: vec![Download {
+                asset: discovery.asset_name,
+                url: discovery.download_url,
+                sha256: Some(install_result.sha256),
+            }],
+            ..Default::default()
+        },
+    })
+}
+
+/// Install exactly the release assets a lockfile recorded, checked against
+/// their recorded digests
+pub fn execute_locked_github_install(
+    tool_name: &str,
+    tool_config: &ToolInstaller,
+    tool: &Tool,
+    locked: &LockedTool,
+    bin_dir: &Path,
+    github: &GitHubClient,
+) -> Result<InstallResult> {
+    use crate::github::{DiscoveryResult, download_and_install, download_locked};
+
+    let provenance = Provenance {
+        tag: locked.tag.clone(),
+        downloads: locked.assets.clone(),
+        ..Default::default()
+    };
+
+    // Pattern installs place each asset in the bin dir as-is
+    if tool_config.pattern.is_some() {
+        let executables = download_locked(&locked.assets, bin_dir, github)?;
+        return Ok(InstallResult {
+            version: locked.version.clone(),
+            executables: Some(executables),
+            provenance,
+        });
+    }
+
+    let [asset] = locked.assets.as_slice() else {
+        anyhow::bail!(
+            "forge.lock should record exactly one asset for {}, found {}",
+            tool_name,
+            locked.assets.len()
+        );
+    };
+    let sha256 = asset
+        .sha256
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("No sha256 recorded for {}", asset.asset))?;
+
+    let discovery = DiscoveryResult {
+        download_url: asset.url.clone(),
+        version: locked.version.clone(),
+        tag: locked.tag.clone().unwrap_or_default(),
+        asset_name: asset.asset.clone(),
+        checksum_url: None,
+    };
+    let install_result = download_and_install(
+        &discovery,
+        tool_name,
+        &tool.provides,
+        Some(sha256),
+        bin_dir,
+        github,
+    )?;
+
+    for exe in &install_result.executables {
+        outln!("  Installed: {}", exe);
+    }
+
+    Ok(InstallResult {
+        version: locked.version.clone(),
+        executables: Some(install_result.executables),
+        provenance,
     })
 }
 
@@ -247,7 +419,101 @@ pub fn expand_template(
     platform.expand_pattern(&expanded)
 }
 
-pub fn check_tool_version(tool_name: &str, command_template: &[String]) -> Result<Option<String>> {
+/// Expand every part of a command template
+pub fn expand_command(
+    template: &[String],
+    tool_name: &str,
+    config: &ToolInstaller,
+    version: Option<&str>,
+    platform: &Platform,
+) -> Vec<String> {
+    template
+        .iter()
+        .map(|part| expand_template(part, tool_name, config, version, platform))
+        .collect()
+}
+
+/// What an install would do, resolved without running anything
+pub enum InstallPlan {
+    /// Command installer invocation, fully expanded
+    Command(Vec<String>),
+    /// Platform script with platform variables expanded
+    Script(String),
+    /// Release download from GitHub, optionally restricted to an asset pattern.
+    /// The release is the one tagged with the requested version, if any
+    GithubRelease {
+        repo: String,
+        pattern: Option<String>,
+    },
+}
+
+/// Refuse a version request the installer has no way to honor
+pub fn ensure_version_supported(
+    installer_name: &str,
+    installer: &Installer,
+    tool_name: &str,
+    version: Option<&str>,
+) -> Result<()> {
+    if let Some(version) = version
+        && installer_name != "github"
+        && installer.install_version.is_none()
+    {
+        anyhow::bail!(
+            "The {} installer cannot install a specific version of {} ({})",
+            installer_name,
+            tool_name,
+            version
+        );
+    }
+    Ok(())
+}
+
+pub fn plan_install(
+    installer_name: &str,
+    installer: &Installer,
+    tool_name: &str,
+    tool_config: &ToolInstaller,
+    version: Option<&str>,
+    platform: &Platform,
+) -> Result<InstallPlan> {
+    ensure_version_supported(installer_name, installer, tool_name, version)?;
+
+    if installer.installer_type == "script" {
+        let scripts = get_platform_scripts(tool_config, platform)
+            .ok_or_else(|| anyhow::anyhow!("No script for {} on {}", tool_name, platform.os))?;
+        return Ok(InstallPlan::Script(
+            platform.expand_pattern(&scripts.install),
+        ));
+    }
+
+    if installer_name == "github" {
+        let repo = tool_config
+            .repo
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("GitHub installer requires 'repo' field"))?;
+        return Ok(InstallPlan::GithubRelease {
+            repo,
+            pattern: tool_config
+                .pattern
+                .as_ref()
+                .map(|p| platform.expand_pattern(p)),
+        });
+    }
+
+    Ok(InstallPlan::Command(expand_command(
+        installer.install_template(version)?,
+        tool_name,
+        tool_config,
+        version,
+        platform,
+    )))
+}
+
+pub fn check_tool_version(
+    tool_name: &str,
+    command_template: &[String],
+    runner: &dyn CommandRunner,
+) -> Result<Option<String>> {
     let command: Vec<String> = command_template
         .iter()
         .map(|part| part.replace("{tool}", tool_name))
@@ -257,7 +523,7 @@ pub fn check_tool_version(tool_name: &str, command_template: &[String]) -> Resul
         return Ok(None);
     }
 
-    let output = Command::new(&command[0]).args(&command[1..]).output()?;
+    let output = runner.run(&command[0], &command[1..])?;
 
     if output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -278,12 +544,11 @@ fn extract_version(output: &str) -> Option<String> {
     ];
 
     for pattern in &patterns {
-        if let Ok(re) = Regex::new(pattern) {
-            if let Some(captures) = re.captures(output) {
-                if let Some(version_match) = captures.get(1) {
-                    return Some(version_match.as_str().to_string());
-                }
-            }
+        if let Ok(re) = Regex::new(pattern)
+            && let Some(captures) = re.captures(output)
+            && let Some(version_match) = captures.get(1)
+        {
+            return Some(version_match.as_str().to_string());
         }
     }
 
@@ -298,7 +563,12 @@ fn extract_with_pattern(text: &str, pattern: &str) -> Option<String> {
         .map(|m| m.as_str().to_string())
 }
 
-fn detect_tool_version(tool_name: &str, tool: &Tool) -> Result<Option<String>> {
+fn detect_tool_version(
+    tool_name: &str,
+    tool: &Tool,
+    bin_dir: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<Option<String>> {
     // Determine which executable to check
     let executable = if !tool.provides.is_empty() {
         &tool.provides[0]
@@ -307,22 +577,20 @@ fn detect_tool_version(tool_name: &str, tool: &Tool) -> Result<Option<String>> {
     };
 
     // First try to run it from PATH
-    if let Some(version) = try_version_commands(executable)? {
+    if let Some(version) = try_version_commands(executable, runner)? {
         return Ok(Some(version));
     }
 
     // If not found on PATH, try ~/.local/bin with full path
-    if let Some(home) = dirs::home_dir() {
-        let exe_path = home.join(".local/bin").join(executable);
-        if exe_path.exists() {
-            return try_version_commands_with_path(&exe_path);
-        }
+    let exe_path = bin_dir.join(executable);
+    if exe_path.exists() {
+        return try_version_commands_with_path(&exe_path, runner);
     }
 
     Ok(None)
 }
 
-fn try_version_commands(executable: &str) -> Result<Option<String>> {
+fn try_version_commands(executable: &str, runner: &dyn CommandRunner) -> Result<Option<String>> {
     // Try common version flag patterns
     let version_flags = [
         vec!["--version"],
@@ -333,15 +601,15 @@ fn try_version_commands(executable: &str) -> Result<Option<String>> {
     ];
 
     for flags in &version_flags {
-        if let Ok(output) = Command::new(executable).args(flags).output() {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                let combined = format!("{}\n{}", stdout, stderr);
+        if let Ok(output) = runner.run(executable, &to_args(flags))
+            && output.status.success()
+        {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let combined = format!("{}\n{}", stdout, stderr);
 
-                if let Some(version) = extract_version(&combined) {
-                    return Ok(Some(version));
-                }
+            if let Some(version) = extract_version(&combined) {
+                return Ok(Some(version));
             }
         }
     }
@@ -349,7 +617,10 @@ fn try_version_commands(executable: &str) -> Result<Option<String>> {
     Ok(None)
 }
 
-fn try_version_commands_with_path(exe_path: &PathBuf) -> Result<Option<String>> {
+fn try_version_commands_with_path(
+    exe_path: &Path,
+    runner: &dyn CommandRunner,
+) -> Result<Option<String>> {
     // Try common version flag patterns with full path
     let version_flags = [
         vec!["--version"],
@@ -361,23 +632,22 @@ fn try_version_commands_with_path(exe_path: &PathBuf) -> Result<Option<String>>
     ];
 
     for flags in &version_flags {
-        match Command::new(exe_path).args(flags).output() {
+        match runner.run(&exe_path.to_string_lossy(), &to_args(flags)) {
             Ok(output) => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 let stderr = String::from_utf8_lossy(&output.stderr);
 
-
                 if output.status.success() {
                     let combined = format!("{}\n{}", stdout, stderr);
                     if let Some(version) = extract_version(&combined) {
                         return Ok(Some(version));
                     } else {
-                        println!("    Failed to extract version from output");
+                        outln!("    Failed to extract version from output");
                     }
                 }
             }
             Err(e) => {
-                println!("    Failed to execute: {}", e);
+                outln!("    Failed to execute: {}", e);
             }
         }
     }
@@ -385,7 +655,11 @@ fn try_version_commands_with_path(exe_path: &PathBuf) -> Result<Option<String>>
     Ok(None)
 }
 
-fn get_platform_scripts<'a>(
+fn to_args(flags: &[&str]) -> Vec<String> {
+    flags.iter().map(|f| f.to_string()).collect()
+}
+
+pub fn get_platform_scripts<'a>(
     tool_installer: &'a ToolInstaller,
     platform: &'a Platform,
 ) -> Option<&'a crate::knowledge::PlatformScripts> {
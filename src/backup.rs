@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Copies of files taken before an install or update replaces them, so a
+/// failure can put everything back the way it was
+#[derive(Debug)]
+pub struct Backup {
+    root: PathBuf,
+    dir: Option<TempDir>,
+    saved: Vec<(PathBuf, PathBuf)>,
+    /// Files that did not exist yet, removed again on restore
+    created: Vec<PathBuf>,
+}
+
+impl Backup {
+    /// An empty backup that keeps its copies in a fresh directory under `root`
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            dir: None,
+            saved: Vec::new(),
+            created: Vec::new(),
+        }
+    }
+
+    /// Remember `file` as it is now, whether or not it exists
+    pub fn save(&mut self, file: &Path) -> Result<()> {
+        let tracked = self.saved.iter().any(|(original, _)| original == file)
+            || self.created.iter().any(|created| created == file);
+        if tracked {
+            return Ok(());
+        }
+
+        if !file.is_file() {
+            self.created.push(file.to_path_buf());
+            return Ok(());
+        }
+
+        let dir = match &self.dir {
+            Some(dir) => dir.path().to_path_buf(),
+            None => {
+                std::fs::create_dir_all(&self.root)?;
+                let dir = tempfile::Builder::new()
+                    .prefix("forge-")
+                    .tempdir_in(&self.root)?;
+                let path = dir.path().to_path_buf();
+                self.dir = Some(dir);
+                path
+            }
+        };
+
+        let copy = dir.join(self.saved.len().to_string());
+        std::fs::copy(file, &copy)
+            .with_context(|| format!("Failed to back up {}", file.display()))?;
+        self.saved.push((file.to_path_buf(), copy));
+        Ok(())
+    }
+
+    /// Whether restoring would change anything on disk
+    pub fn is_empty(&self) -> bool {
+        self.saved.is_empty() && !self.created.iter().any(|file| file.exists())
+    }
+
+    /// Put every saved file back where it was and remove files that were new
+    pub fn restore(self) -> Result<()> {
+        for file in &self.created {
+            if file.exists() {
+                std::fs::remove_file(file)?;
+            }
+        }
+
+        for (original, copy) in &self.saved {
+            copy_into_place(copy, original)?;
+        }
+        Ok(())
+    }
+
+    /// Each saved file's original location and the copy taken of it
+    pub fn saved(&self) -> &[(PathBuf, PathBuf)] {
+        &self.saved
+    }
+
+    /// Drop the copies once the new files are confirmed
+    pub fn discard(self) {}
+}
+
+/// Copy `source` over `dest` by way of a sibling file and a rename, so a
+/// running binary is replaced rather than written into
+pub fn copy_into_place(source: &Path, dest: &Path) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let staged = dest.with_extension("forge-restore");
+    std::fs::copy(source, &staged)?;
+    std::fs::rename(&staged, dest)
+        .with_context(|| format!("Failed to restore {}", dest.display()))?;
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::output::outln;
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+
+/// Lowercase hex sha256 of a file's contents
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Lowercase hex sha256 of in-memory data
+pub fn sha256_bytes(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Whether a release asset name looks like a checksum listing
+pub fn is_checksum_asset(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.ends_with(".sha256")
+        || name.ends_with(".sha256sum")
+        || name.contains("sha256sums")
+        || name.contains("checksums")
+}
+
+/// Pick the checksum asset that covers `asset_name`, preferring a dedicated
+/// `<asset>.sha256` file over a release-wide listing
+pub fn find_checksum_asset<'a>(names: &[&'a str], asset_name: &str) -> Option<&'a str> {
+    let dedicated = [
+        format!("{}.sha256", asset_name),
+        format!("{}.sha256sum", asset_name),
+    ];
+
+    names
+        .iter()
+        .find(|name| dedicated.iter().any(|d| d == *name))
+        .or_else(|| {
+            // Signatures and other assets' own digests don't cover this one
+            const NOT_A_LISTING: [&str; 5] = [".sig", ".asc", ".sha256", ".sha256sum", ".sha512"];
+            names.iter().find(|name| {
+                let lower = name.to_lowercase();
+                is_checksum_asset(name) && !NOT_A_LISTING.iter().any(|ext| lower.ends_with(ext))
+            })
+        })
+        .copied()
+}
+
+/// Find the digest for `asset_name` in checksum file contents
+///
+/// Understands `sha256sum` output (`<hex>  <name>`, optionally `*<name>`) as
+/// well as files holding nothing but the digest of a single asset.
+pub fn parse_checksum(contents: &str, asset_name: &str) -> Option<String> {
+    let mut lone_digest = None;
+    let mut lines = 0;
+
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        lines += 1;
+        let mut parts = line.split_whitespace();
+        let Some(digest) = parts.next().filter(|d| is_sha256(d)) else {
+            continue;
+        };
+
+        match parts.next() {
+            Some(name) => {
+                let name = name.trim_start_matches('*').trim_start_matches("./");
+                let file_name = name.rsplit('/').next().unwrap_or(name);
+                if file_name == asset_name {
+                    return Some(digest.to_lowercase());
+                }
+            }
+            None => lone_digest = Some(digest.to_lowercase()),
+        }
+    }
+
+    if lines == 1 { lone_digest } else { None }
+}
+
+/// Compare a file against an expected digest, refusing on mismatch
+pub fn verify_sha256(path: &Path, expected: &str, source: &str) -> Result<()> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    ensure_digest(&name, &sha256_file(path)?, expected, source)
+}
+
+/// Compare an already computed digest against an expected one
+pub fn ensure_digest(name: &str, actual: &str, expected: &str, source: &str) -> Result<()> {
+    let expected = expected.trim().to_lowercase();
+
+    if actual != expected {
+        anyhow::bail!(
+            "Checksum mismatch for {} ({})\n  expected: {}\n  actual:   {}\nRefusing to install",
+            name,
+            source,
+            expected,
+            actual
+        );
+    }
+
+    outln!("  Verified sha256 ({})", source);
+    Ok(())
+}
+
+fn is_sha256(s: &str) -> bool {
+    s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
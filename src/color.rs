@@ -6,23 +6,23 @@ impl Colors {
     pub fn action(s: &str) -> ColoredString {
         s.blue()
     }
-    
+
     pub fn success(s: &str) -> ColoredString {
         s.green()
     }
-    
+
     pub fn warning(s: &str) -> ColoredString {
         s.yellow()
     }
-    
+
     pub fn error(s: &str) -> ColoredString {
         s.red()
     }
-    
+
     pub fn info(s: &str) -> ColoredString {
         s.cyan()
     }
-    
+
     pub fn muted(s: &str) -> ColoredString {
         s.dimmed()
     }
@@ -37,4 +37,5 @@ pub const ERROR: &str = "❌";
 pub const TIP: &str = "💡";
 pub const SEARCH: &str = "🔍";
 pub const RUNNING: &str = "⏳";
-pub const PACKAGE: &str = "📦";
\ No newline at end of file
+pub const PACKAGE: &str = "📦";
+pub const DRY_RUN: &str = "🧪";
@@ -1,9 +1,15 @@
 use anyhow::Result;
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
 
 /// Trait for running system commands - allows mocking in tests
 pub trait CommandRunner: Send + Sync {
     fn run(&self, program: &str, args: &[String]) -> Result<Output>;
+
+    /// Run a command with `input` written to its stdin
+    fn run_with_input(&self, program: &str, args: &[String], input: &str) -> Result<Output> {
+        let _ = input;
+        self.run(program, args)
+    }
 }
 
 /// Real command runner that executes actual system commands
@@ -13,6 +19,23 @@ impl CommandRunner for SystemCommandRunner {
     fn run(&self, program: &str, args: &[String]) -> Result<Output> {
         Ok(Command::new(program).args(args).output()?)
     }
+
+    fn run_with_input(&self, program: &str, args: &[String], input: &str) -> Result<Output> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Write content to stdin, closing it so the child sees EOF
+        use std::io::Write;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes())?;
+        }
+
+        Ok(child.wait_with_output()?)
+    }
 }
 
 /// Mock command runner for testing
@@ -24,6 +47,7 @@ pub mod mock {
 
     pub struct MockCommandRunner {
         expectations: Mutex<HashMap<String, MockExpectation>>,
+        calls: Mutex<Vec<String>>,
     }
 
     pub struct MockExpectation {
@@ -33,10 +57,17 @@ pub mod mock {
         pub success: bool,
     }
 
+    impl Default for MockCommandRunner {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
     impl MockCommandRunner {
         pub fn new() -> Self {
             Self {
                 expectations: Mutex::new(HashMap::new()),
+                calls: Mutex::new(Vec::new()),
             }
         }
 
@@ -53,12 +84,18 @@ pub mod mock {
                 },
             );
         }
+
+        /// Every command run so far, formatted as `program arg1 arg2`
+        pub fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
     }
 
     impl CommandRunner for MockCommandRunner {
         fn run(&self, program: &str, args: &[String]) -> Result<Output> {
             let expectations = self.expectations.lock().unwrap();
             let key = format!("{} {}", program, args.join(" "));
+            self.calls.lock().unwrap().push(key.clone());
 
             let expectation = expectations
                 .get(&key)
@@ -0,0 +1,94 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// One result of `forge doctor`, with a command that fixes it when there is one
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub category: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    pub fn ok(category: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            category,
+            severity: Severity::Ok,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    pub fn warning(
+        category: &'static str,
+        message: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            category,
+            severity: Severity::Warning,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn error(
+        category: &'static str,
+        message: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            category,
+            severity: Severity::Error,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn is_problem(&self) -> bool {
+        self.severity != Severity::Ok
+    }
+}
+
+/// Whether `dir` is one of the entries of a PATH-style search path
+pub fn path_contains(search_path: &OsStr, dir: &Path) -> bool {
+    std::env::split_paths(search_path).any(|entry| entry == dir)
+}
+
+/// The binary that actually runs for `name`, if it is not `expected`
+pub fn shadowing_binary(name: &str, expected: &Path, search_path: &OsStr) -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    let first = which::which_in_all(name, Some(search_path), cwd)
+        .ok()?
+        .next()?;
+
+    let same = first == expected
+        || matches!(
+            (first.canonicalize(), expected.canonicalize()),
+            (Ok(a), Ok(b)) if a == b
+        );
+    (!same).then_some(first)
+}
+
+/// Shell snippet that puts `dir` on PATH for the user's shell
+pub fn path_fix(dir: &Path, os: &str) -> String {
+    let rc = if os == "macos" {
+        "~/.zshrc"
+    } else {
+        "~/.bashrc"
+    };
+    format!(
+        "echo 'export PATH=\"{}:$PATH\"' >> {} && source {}",
+        dir.display(),
+        rc,
+        rc
+    )
+}
@@ -1,8 +1,10 @@
+use crate::paths::Paths;
 use crate::sync::SyncConfig;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Facts {
@@ -20,14 +22,86 @@ pub struct ToolFact {
     pub version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub executables: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Set by `forge pin` to hold the tool back from updates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin: Option<Pin>,
+}
+
+/// An update hold, either total or limited to a version range
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Pin {
+    /// Version prefix updates may stay within, such as `1.28` for any `1.28.x`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<String>,
+}
+
+impl Pin {
+    /// Whether an update to `version` is allowed. The range matches whole
+    /// version components, so `1.2` allows `1.2.5` but not `1.20.0`.
+    pub fn allows(&self, version: &str) -> bool {
+        let Some(range) = &self.range else {
+            return false;
+        };
+        let range = range.trim_start_matches('v').trim_end_matches(".*");
+        let version = version.trim_start_matches('v');
+        version == range
+            || version
+                .strip_prefix(range)
+                .is_some_and(|rest| rest.starts_with(['.', '-', '+', '_']))
+    }
+}
+
+impl std::fmt::Display for Pin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.range {
+            Some(range) => write!(f, "pinned to {}", range),
+            None => write!(f, "pinned"),
+        }
+    }
+}
+
+/// Where an installed tool came from, resolved at install time
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Provenance {
+    /// Release tag the assets were taken from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub downloads: Vec<Download>,
+    /// Fully expanded install command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+    /// sha256 of the expanded install script
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_sha256: Option<String>,
+    /// Installed executables by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub executables: BTreeMap<String, ExecutableRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Download {
+    pub asset: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ExecutableRecord {
+    pub path: PathBuf,
+    pub sha256: String,
 }
 
 impl Facts {
     pub async fn load() -> Result<Self> {
-        let path = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-            .join(".forge")
-            .join("facts.toml");
+        Self::load_from(&Paths::from_home_dir()?).await
+    }
+
+    pub async fn load_from(paths: &Paths) -> Result<Self> {
+        let path = paths.facts_file();
 
         if !path.exists() {
             return Ok(Self::default());
@@ -38,10 +112,11 @@ impl Facts {
     }
 
     pub async fn save(&self) -> Result<()> {
-        let path = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-            .join(".forge")
-            .join("facts.toml");
+        self.save_to(&Paths::from_home_dir()?).await
+    }
+
+    pub async fn save_to(&self, paths: &Paths) -> Result<()> {
+        let path = paths.facts_file();
 
         if let Some(parent) = path.parent() {
             tokio::fs::create_dir_all(parent).await?;
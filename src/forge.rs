@@ -1,94 +1,425 @@
 use crate::{
-    backend::execute_install,
-    color::{ACTION, Colors, INFO, SEARCH, SUCCESS, WARNING},
-    facts::{Facts, ToolFact},
-    knowledge::{Knowledge, Tool},
+    backend::{
+        InstallPlan, execute_install_with_runner, expand_command, get_platform_scripts,
+        plan_install,
+    },
+    backup::Backup,
+    color::{ACTION, Colors, DRY_RUN, ERROR, INFO, SEARCH, SUCCESS, TIP, WARNING},
+    command::{CommandRunner, SystemCommandRunner},
+    facts::{ExecutableRecord, Facts, Pin, Provenance, ToolFact},
+    github::{GitHubClient, discover_asset},
+    history::History,
+    knowledge::{Knowledge, Tool, ToolInstaller},
+    lock::LockedTool,
+    output::{self, outln},
+    paths::Paths,
     platform::Platform,
+    report::{
+        Candidate, CandidateStatus, Installation, InstalledTool, PlanReport, PlannedAction,
+        ToolInfo, ToolList,
+    },
+    search::{Location, Provider, SearchResult},
+    staging::Staging,
     sync::SyncConfig,
+    updates::{UpdateCheck, UpdateStatus},
     version::check_latest_version,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use std::{path::Path, process::Command};
+use futures::stream::{self, StreamExt};
+use std::{collections::BTreeMap, future::Future, path::PathBuf, sync::Arc};
 
+/// Default for how many version checks and installs run at once
+pub const DEFAULT_JOBS: usize = 8;
+
+#[derive(Clone)]
 pub struct Forge {
-    knowledge: Knowledge,
+    knowledge: Arc<Knowledge>,
     platform: Platform,
+    runner: Arc<dyn CommandRunner>,
+    github: GitHubClient,
+    paths: Paths,
+    dry_run: bool,
+    jobs: usize,
+    /// Held while facts.toml is loaded, changed and saved, so parallel
+    /// installs don't overwrite each other's records
+    facts_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+/// Installer chosen for a tool from the platform precedence
+struct InstallerSelection<'a> {
+    installer: String,
+    tool_installer: &'a ToolInstaller,
+    /// Candidates passed over before the chosen one, with the reason
+    skipped: Vec<(String, String)>,
 }
 
 impl Forge {
     pub async fn new() -> Result<Self> {
-        let knowledge = Knowledge::load().await?;
+        Self::with_environment(Arc::new(SystemCommandRunner), Paths::from_home_dir()?).await
+    }
+
+    /// Build a Forge that runs every subprocess through `runner` and keeps
+    /// its state and executables under `paths`
+    pub async fn with_environment(runner: Arc<dyn CommandRunner>, paths: Paths) -> Result<Self> {
+        let knowledge = Knowledge::load_from(&paths).await?;
         let platform = Platform::detect()?;
         Ok(Self {
-            knowledge,
+            knowledge: Arc::new(knowledge),
             platform,
+            runner,
+            github: GitHubClient::from_env(),
+            paths,
+            dry_run: false,
+            jobs: DEFAULT_JOBS,
+            facts_lock: Arc::new(tokio::sync::Mutex::new(())),
         })
     }
 
-    pub async fn install(&self, tool_name: &str, installer_name: Option<&str>) -> Result<()> {
-        println!("{} Installing {}...", INFO, Colors::info(tool_name));
+    /// Talk to a different GitHub API endpoint, e.g. a local mock server
+    pub fn github_client(mut self, client: GitHubClient) -> Self {
+        self.github = client;
+        self
+    }
 
-        // Load facts
-        let mut facts = Facts::load().await?;
+    /// Report what install, update and uninstall would do without doing it
+    pub fn dry_run(mut self, enabled: bool) -> Self {
+        self.dry_run = enabled;
+        self
+    }
 
-        // Check if already installed
-        if let Some(fact) = facts.tools.get(tool_name) {
-            // Check if we're trying to use a different installer
-            if let Some(requested_installer) = installer_name {
-                if requested_installer != fact.installer {
-                    // User explicitly wants a different installer
-                    println!(
-                        "{} {} is already installed via {} (v{})",
-                        WARNING,
-                        tool_name,
-                        Colors::warning(&fact.installer),
-                        Colors::muted(fact.version.as_deref().unwrap_or("unknown"))
+    /// How many version checks and independent installs may run at once
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Install a tool, or a specific version of it with `tool@version`
+    pub async fn install(&self, tool_spec: &str, installer_name: Option<&str>) -> Result<()> {
+        let (tool_name, version) = split_version(tool_spec);
+        self.install_with(tool_name, installer_name, version, None)
+            .await
+    }
+
+    /// Install several tools in one go. Every name is resolved before anything
+    /// is installed, and a failure doesn't stop the rest.
+    pub async fn install_all(
+        &self,
+        tool_specs: &[String],
+        installer_name: Option<&str>,
+    ) -> Result<()> {
+        let mut specs: Vec<(String, String)> = Vec::new();
+        let mut unknown = Vec::new();
+        for spec in tool_specs {
+            let (name, _) = split_version(spec);
+            if !self.knowledge.tools.contains_key(name) {
+                unknown.push(name);
+            } else if !specs.iter().any(|(t, _)| t == name) {
+                specs.push((name.to_string(), spec.clone()));
+            }
+        }
+        if !unknown.is_empty() {
+            anyhow::bail!("Unknown tools: {}", unknown.join(", "));
+        }
+
+        if let [(_, spec)] = specs.as_slice() {
+            return self.install(spec, installer_name).await;
+        }
+
+        // Plan every tool before installing any
+        let facts = Facts::load_from(&self.paths).await?;
+        let rounds = self.install_rounds(&specs);
+        let width = specs.iter().map(|(t, _)| t.len()).max().unwrap_or(0);
+        outln!("{} Planning {} tools...", SEARCH, specs.len());
+        let mut installers: BTreeMap<String, String> = BTreeMap::new();
+        for (tool_name, spec) in rounds.iter().flatten() {
+            let installer = installer_name
+                .map(str::to_string)
+                .or_else(|| self.resolve_installer(tool_name));
+            let note = match (facts.tools.get(tool_name), &installer) {
+                (Some(fact), _) if split_version(spec).1.is_none() => format!(
+                    "installed (v{})",
+                    fact.version.as_deref().unwrap_or("unknown")
+                ),
+                (_, Some(installer)) => installer.clone(),
+                (_, None) => "no usable installer yet".to_string(),
+            };
+            outln!(
+                "  {:width$}  {}",
+                Colors::info(tool_name),
+                Colors::muted(&note),
+                width = width
+            );
+            installers.insert(tool_name.clone(), installer.unwrap_or_default());
+        }
+
+        let mut results: BTreeMap<String, Result<()>> = BTreeMap::new();
+        for round in rounds {
+            let jobs = round
+                .iter()
+                .map(|(tool_name, _)| (tool_name.clone(), installers[tool_name].clone()))
+                .collect();
+            let round_specs: BTreeMap<String, String> = round.into_iter().collect();
+            let installer_name = installer_name.map(str::to_string);
+            let finished = self
+                .run_jobs(jobs, move |forge, tool_name| {
+                    let spec = round_specs[&tool_name].clone();
+                    let installer_name = installer_name.clone();
+                    async move {
+                        outln!();
+                        forge.install(&spec, installer_name.as_deref()).await
+                    }
+                })
+                .await;
+            results.extend(finished);
+        }
+
+        // Summary, in the order the tools were asked for
+        let facts = Facts::load_from(&self.paths).await?;
+        outln!("\nSummary:");
+        let mut failed = Vec::new();
+        for (tool_name, _) in &specs {
+            match &results[tool_name] {
+                Ok(()) => {
+                    let detail = facts
+                        .tools
+                        .get(tool_name)
+                        .map(|fact| {
+                            format!(
+                                "v{} ({})",
+                                fact.version.as_deref().unwrap_or("unknown"),
+                                fact.installer
+                            )
+                        })
+                        .unwrap_or_default();
+                    outln!(
+                        "  {} {:width$}  {}",
+                        SUCCESS,
+                        Colors::success(tool_name),
+                        Colors::muted(&detail),
+                        width = width
                     );
-                    println!(
-                        "{} Switching to {} installer...",
-                        ACTION,
-                        Colors::action(requested_installer)
+                }
+                Err(e) => {
+                    outln!(
+                        "  {} {:width$}  {}",
+                        ERROR,
+                        Colors::error(tool_name),
+                        e,
+                        width = width
                     );
+                    failed.push(tool_name.as_str());
+                }
+            }
+        }
 
-                    // Uninstall the old version first
-                    println!(
-                        "{} Uninstalling {} ({})...",
-                        ACTION,
-                        Colors::warning(tool_name),
-                        fact.installer
-                    );
+        if !failed.is_empty() {
+            anyhow::bail!(
+                "Failed to install {} of {} tools: {}",
+                failed.len(),
+                specs.len(),
+                failed.join(", ")
+            );
+        }
+        if self.dry_run {
+            outln!("\n{} Dry run complete, nothing was changed", DRY_RUN);
+        }
+        Ok(())
+    }
+
+    /// Group `(tool, spec)` pairs into rounds, so a tool providing an
+    /// installer another one can use (rust for cargo) goes a round earlier
+    fn install_rounds(&self, specs: &[(String, String)]) -> Vec<Vec<(String, String)>> {
+        let needs = |tool: &str, other: &str| {
+            let (Some(tool_def), Some(other_def)) = (
+                self.knowledge.tools.get(tool),
+                self.knowledge.tools.get(other),
+            ) else {
+                return false;
+            };
+            tool != other
+                && other_def
+                    .provides
+                    .iter()
+                    .any(|provided| tool_def.installers.contains_key(provided))
+        };
 
-                    // Perform uninstall (it handles facts removal)
-                    self.uninstall(tool_name).await?;
+        let mut remaining = specs.to_vec();
+        let mut rounds = Vec::new();
+        while !remaining.is_empty() {
+            let (ready, waiting): (Vec<_>, Vec<_>) = remaining
+                .iter()
+                .cloned()
+                .partition(|(t, _)| !remaining.iter().any(|(other, _)| needs(t, other)));
+            // A cycle can't be ordered; install the rest as given
+            if ready.is_empty() {
+                rounds.push(waiting);
+                break;
+            }
+            rounds.push(ready);
+            remaining = waiting;
+        }
+        rounds
+    }
 
-                    // Restore the fact if uninstall fails
-                    // (uninstall removes it from facts, but we already removed it)
+    /// Install a tool, taking exact release assets from a lockfile entry when
+    /// one is given
+    async fn install_with(
+        &self,
+        tool_name: &str,
+        installer_name: Option<&str>,
+        version: Option<&str>,
+        locked: Option<&LockedTool>,
+    ) -> Result<()> {
+        self.install_transaction(tool_name, installer_name, version, locked, true, false)
+            .await
+    }
 
-                    println!("{} Uninstalled {}", SUCCESS, Colors::success(tool_name));
-                    // Continue with installation below
-                } else {
-                    // Same installer requested - skip
-                    println!(
+    /// Run an install that puts back the files it replaced if any step fails.
+    /// facts.toml is only written once the new install is in place. With
+    /// `keep_history`, a replaced version is kept for rollback; with
+    /// `reinstall`, a tool already at the requested version is installed again.
+    async fn install_transaction(
+        &self,
+        tool_name: &str,
+        installer_name: Option<&str>,
+        version: Option<&str>,
+        locked: Option<&LockedTool>,
+        keep_history: bool,
+        reinstall: bool,
+    ) -> Result<()> {
+        let snapshot = Facts::load_from(&self.paths).await?;
+        let mut backup = Backup::new(&self.paths.backup_dir());
+
+        let result = self
+            .install_steps(
+                tool_name,
+                installer_name,
+                version,
+                locked,
+                reinstall,
+                &mut backup,
+            )
+            .await;
+        if result.is_ok() {
+            if keep_history
+                && !self.dry_run
+                && let Some(previous) = snapshot.tools.get(tool_name)
+            {
+                self.remember_replaced(tool_name, previous, &backup).await?;
+            }
+            backup.discard();
+            return result;
+        }
+
+        if !backup.is_empty() {
+            outln!("{} Restoring the previous files...", WARNING);
+        }
+        backup.restore()?;
+        result
+    }
+
+    async fn install_steps(
+        &self,
+        tool_name: &str,
+        installer_name: Option<&str>,
+        version: Option<&str>,
+        locked: Option<&LockedTool>,
+        reinstall: bool,
+        backup: &mut Backup,
+    ) -> Result<()> {
+        match version {
+            Some(version) => outln!(
+                "{} Installing {} v{}...",
+                INFO,
+                Colors::info(tool_name),
+                version
+            ),
+            None => outln!("{} Installing {}...", INFO, Colors::info(tool_name)),
+        }
+
+        // Load facts
+        let facts = Facts::load_from(&self.paths).await?;
+
+        // A version change reinstalls with the installer already in use
+        let mut previous_installer = None;
+        // A pin survives reinstalling the tool
+        let pin = facts.tools.get(tool_name).and_then(|f| f.pin.clone());
+        // The install being replaced, whose uninstaller runs once the new one is in
+        let mut replaced = None;
+
+        // Check if already installed
+        if let Some(fact) = facts.tools.get(tool_name) {
+            let other_installer = installer_name.filter(|name| *name != fact.installer);
+            let other_version = version.filter(|v| {
+                fact.version.as_deref().map(|f| f.trim_start_matches('v'))
+                    != Some(v.trim_start_matches('v'))
+            });
+
+            if !reinstall && other_installer.is_none() && other_version.is_none() {
+                match installer_name {
+                    Some(_) => outln!(
                         "{} {} is already installed via {} (v{})",
                         SUCCESS,
                         tool_name,
                         Colors::info(&fact.installer),
                         Colors::muted(fact.version.as_deref().unwrap_or("unknown"))
-                    );
-                    return Ok(());
+                    ),
+                    None => outln!(
+                        "{} {} is already installed (v{})",
+                        SUCCESS,
+                        tool_name,
+                        Colors::muted(fact.version.as_deref().unwrap_or("unknown"))
+                    ),
                 }
-            } else {
-                // No specific installer requested - keep existing
-                println!(
-                    "{} {} is already installed (v{})",
-                    SUCCESS,
+                return Ok(());
+            }
+
+            if !reinstall {
+                outln!(
+                    "{} {} is already installed via {} (v{})",
+                    WARNING,
                     tool_name,
+                    Colors::warning(&fact.installer),
                     Colors::muted(fact.version.as_deref().unwrap_or("unknown"))
                 );
-                return Ok(());
             }
+            match (other_installer, other_version) {
+                (Some(requested_installer), _) => outln!(
+                    "{} Switching to {} installer...",
+                    ACTION,
+                    Colors::action(requested_installer)
+                ),
+                (None, Some(requested_version)) => {
+                    outln!(
+                        "{} Switching to v{}...",
+                        ACTION,
+                        Colors::action(requested_version)
+                    );
+                    previous_installer = Some(fact.installer.clone());
+                }
+                (None, None) => {
+                    outln!(
+                        "{} Reinstalling {} via {} (v{})...",
+                        ACTION,
+                        Colors::info(tool_name),
+                        fact.installer,
+                        fact.version.as_deref().unwrap_or("unknown")
+                    );
+                    previous_installer = Some(fact.installer.clone());
+                }
+            }
+
+            // Clear the old executables out of the bin dir, keeping copies
+            // until the new install is in place. The old record stays until
+            // the new one replaces it.
+            if !self.dry_run {
+                self.back_up_executables(tool_name, fact, backup)?;
+            }
+            self.remove_executables(tool_name, fact)?;
+            replaced = Some(fact.clone());
         }
+        let installer_name = installer_name.or(previous_installer.as_deref());
 
         // Find tool
         let tool = self
@@ -99,15 +430,28 @@ impl Forge {
 
         // Find installer - with platform awareness
         let (installer_key, tool_installer) = if let Some(name) = installer_name {
-            tool.installers
-                .get(name)
-                .map(|ti| (name.to_string(), ti))
-                .ok_or_else(|| {
-                    anyhow::anyhow!("{} doesn't support installer: {}", tool_name, name)
-                })?
+            let tool_installer = tool.installers.get(name).ok_or_else(|| {
+                anyhow::anyhow!("{} doesn't support installer: {}", tool_name, name)
+            })?;
+
+            // An explicit choice is honored, but it still has to be usable
+            if let Err(reason) = self.probe_installer(name, tool_installer) {
+                return Err(self.missing_installer(name, &reason));
+            }
+
+            (name.to_string(), tool_installer)
         } else {
             // Use platform precedence
-            self.find_best_installer(tool_name, tool)?
+            let selection = self.find_best_installer(tool_name, tool)?;
+            for (skipped, reason) in &selection.skipped {
+                outln!(
+                    "{} Skipping {} installer: {}",
+                    WARNING,
+                    Colors::warning(skipped),
+                    Colors::muted(reason)
+                );
+            }
+            (selection.installer, selection.tool_installer)
         };
 
         let installer = self
@@ -116,47 +460,39 @@ impl Forge {
             .get(&installer_key)
             .ok_or_else(|| anyhow::anyhow!("Unknown installer: {}", installer_key))?;
 
-        println!(
+        outln!(
             "{} Using {} installer",
             ACTION,
             Colors::action(&installer_key)
         );
 
-        // Check if installer is available (skip for script installers)
-        if installer.installer_type != "script" {
-            if let Some(check) = &installer.check {
-                let result = Command::new(&check[0]).args(&check[1..]).output();
-
-                if result.is_err() || !result.unwrap().status.success() {
-                    // Look for a tool that provides this installer
-                    if let Some(provider) = self.find_tool_that_provides(&installer_key) {
-                        println!(
-                            "\n{} {} installer not available",
-                            crate::color::ERROR,
-                            installer_key
-                        );
-                        println!(
-                            "\n{} {} is provided by: {}",
-                            crate::color::TIP,
-                            installer_key,
-                            Colors::info(&provider.0)
-                        );
-                        println!("   {}", Colors::muted(&provider.1.description));
-                        println!("\nInstall it with:");
-                        println!(
-                            "   {}",
-                            Colors::action(&format!("forge install {}", provider.0))
-                        );
-
-                        anyhow::bail!("Missing installer");
-                    } else {
-                        anyhow::bail!(
-                            "{} installer not available. Please install it first.",
-                            installer_key
-                        );
-                    }
-                }
+        // A lock entry pins the version, so an installer that can't request
+        // one is refused before it installs whatever is newest
+        if let Some(entry) = locked {
+            crate::backend::ensure_version_supported(
+                &installer_key,
+                installer,
+                tool_name,
+                Some(&entry.version),
+            )?;
+        }
+        let version = version.or_else(|| {
+            locked
+                .filter(|_| installer.install_version.is_some())
+                .map(|entry| entry.version.as_str())
+        });
+        crate::backend::ensure_version_supported(&installer_key, installer, tool_name, version)?;
+
+        // Switching installers leaves the old package behind until its own
+        // uninstaller removes it; the same installer replaces it in place
+        let replaced = replaced.filter(|old| old.installer != installer_key);
+
+        if self.dry_run {
+            self.preview_install(tool_name, &installer_key, version)?;
+            if let Some(old) = &replaced {
+                self.run_uninstaller(tool_name, old)?;
             }
+            return Ok(());
         }
 
         // Execute installation and capture version
@@ -175,55 +511,126 @@ impl Forge {
                 anyhow::anyhow!("No script for {} on {}", tool_name, self.platform.os)
             })?;
 
+            // Scripts write where they like; keep what they may overwrite here
+            for exe in &tool.provides {
+                backup.save(&self.paths.bin_dir().join(exe))?;
+            }
+
             crate::backend::execute_script_install(
                 &scripts.install,
                 tool_name,
                 &self.platform,
                 tool,
                 tool_installer,
+                self.paths.bin_dir(),
+                self.runner.as_ref(),
             )?
         } else if installer_key == "github" {
-            // Use smart GitHub installer
-            crate::backend::execute_github_install(tool_name, tool_installer, tool, &self.platform)?
+            // Download everything first, then move it into place together
+            let staging = Staging::new(&self.paths.staging_dir())?;
+            let result = match locked {
+                Some(locked) => crate::backend::execute_locked_github_install(
+                    tool_name,
+                    tool_installer,
+                    tool,
+                    locked,
+                    staging.path(),
+                    &self.github,
+                )?,
+                // Use smart GitHub installer
+                None => crate::backend::execute_github_install(
+                    tool_name,
+                    tool_installer,
+                    tool,
+                    &self.platform,
+                    version,
+                    staging.path(),
+                    &self.github,
+                )?,
+            };
+            staging.commit(self.paths.bin_dir(), backup)?;
+            result
         } else {
-            execute_install(installer, tool_name, tool_installer, None, &self.platform)?
+            execute_install_with_runner(
+                installer,
+                tool_name,
+                tool_installer,
+                version,
+                &self.platform,
+                self.runner.as_ref(),
+            )?
         };
 
-        // Record in facts
-        facts.tools.insert(
-            tool_name.to_string(),
-            ToolFact {
-                installed_at: Utc::now(),
-                installer: installer_key.clone(),
-                version: Some(result.version.clone()),
-                executables: result.executables.clone(),
-            },
-        );
-        facts.save().await?;
+        if let Some(locked) = locked
+            && result.version != locked.version
+        {
+            anyhow::bail!(
+                "{} installed v{}, but forge.lock pins v{}",
+                installer_key,
+                result.version,
+                locked.version
+            );
+        }
+
+        // Record in facts, along with where every binary came from
+        let executable_names = match &result.executables {
+            Some(names) => names.clone(),
+            None if !tool.provides.is_empty() => tool.provides.clone(),
+            None => vec![tool_name.to_string()],
+        };
+        let provenance = Provenance {
+            executables: self.record_executables(&executable_names),
+            ..result.provenance
+        };
+
+        let fact = ToolFact {
+            installed_at: Utc::now(),
+            installer: installer_key.clone(),
+            version: Some(result.version.clone()),
+            executables: result.executables.clone(),
+            provenance: Some(provenance),
+            pin,
+        };
+        let installed: Vec<PathBuf> = fact
+            .provenance
+            .iter()
+            .flat_map(|p| p.executables.values())
+            .map(|record| record.path.clone())
+            .collect();
+        self.edit_facts(|facts| facts.tools.insert(tool_name.to_string(), fact))
+            .await?;
+
+        if let Some(old) = replaced
+            && let Err(e) = self.retire_installation(tool_name, &old, &installed)
+        {
+            outln!(
+                "{} Could not remove the {} install of {}: {}",
+                WARNING,
+                old.installer,
+                tool_name,
+                e
+            );
+        }
 
         // Success message
         if installer.installer_type == "script" {
-            println!(
+            outln!(
                 "{} {} installed successfully!",
                 SUCCESS,
                 Colors::success(tool_name)
             );
 
             // Add PATH reminder if needed
-            if let Some(home) = dirs::home_dir() {
-                let bin_path = home.join(".local/bin");
-                if let Ok(path_var) = std::env::var("PATH") {
-                    if !path_var.split(':').any(|p| Path::new(p) == bin_path) {
-                        println!(
-                            "\n{} Ensure {} is in your PATH",
-                            crate::color::TIP,
-                            Colors::muted(&bin_path.display().to_string())
-                        );
-                    }
-                }
+            let bin_path = self.paths.bin_dir();
+            if !crate::doctor::path_contains(&self.paths.search_path(), bin_path) {
+                outln!(
+                    "\n{} Ensure {} is in your PATH",
+                    crate::color::TIP,
+                    Colors::muted(&bin_path.display().to_string())
+                );
             }
         } else {
-            println!(
+            outln!(
                 "{} {} v{} installed successfully!",
                 SUCCESS,
                 Colors::success(tool_name),
@@ -235,80 +642,107 @@ impl Forge {
     }
 
     pub async fn update(&self, tool_name: Option<&str>, tools_only: bool) -> Result<()> {
-        let facts = Facts::load().await?;
+        let facts = Facts::load_from(&self.paths).await?;
 
         if facts.tools.is_empty() {
-            println!("{}", Colors::muted("No tools installed yet."));
+            outln!("{}", Colors::muted("No tools installed yet."));
             return Ok(());
         }
 
-        let tools_to_check: Vec<(String, ToolFact)> = if let Some(name) = tool_name {
-            if let Some(fact) = facts.tools.get(name) {
-                vec![(name.to_string(), fact.clone())]
-            } else {
-                anyhow::bail!("{} is not installed", name);
-            }
-        } else {
-            facts
-                .tools
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect()
-        };
+        if let Some(name) = tool_name
+            && let Some(fact) = facts.tools.get(name)
+            && let Some(pin) = &fact.pin
+            && pin.range.is_none()
+        {
+            outln!(
+                "{} {} is pinned at v{}. Run {} to update it",
+                INFO,
+                Colors::info(name),
+                fact.version.as_deref().unwrap_or("unknown"),
+                Colors::action(&format!("forge unpin {}", name))
+            );
+            return Ok(());
+        }
 
-        println!("{} Checking for updates...", SEARCH);
+        outln!("{} Checking for updates...", SEARCH);
 
+        let checks = self.update_checks(tool_name).await?;
         let mut updates = Vec::new();
-
-        for (name, fact) in &tools_to_check {
-            if let Some(tool) = self.knowledge.tools.get(name) {
-                let current = fact.version.as_deref().unwrap_or("unknown");
-
-                // Check latest version
-                let installer = self.knowledge.installers.get(&fact.installer);
-                let tool_installer = tool.installers.get(&fact.installer);
-                let package = tool_installer
-                    .and_then(|ti| ti.package.as_ref())
-                    .unwrap_or(name);
-
-                let latest = if let Some(inst) = installer {
-                    check_latest_version(&fact.installer, package, inst.version_check.as_ref())
-                        .await?
-                } else {
-                    None
-                };
-
-                let has_update = match (&fact.version, &latest) {
-                    (Some(c), Some(l)) => c != l,
-                    _ => false,
-                };
-
-                if has_update {
-                    println!(
+        let mut unchecked = Vec::new();
+
+        for check in &checks {
+            let name = &check.name;
+            let current = check.current.as_deref().unwrap_or("unknown");
+            match check.status {
+                // A pin without a range holds the tool where it is
+                UpdateStatus::Pinned => outln!(
+                    "  {} {} {}",
+                    Colors::info(name),
+                    Colors::muted(current),
+                    Colors::warning("(pinned)")
+                ),
+                UpdateStatus::Held => {
+                    let pin = facts.tools.get(name).and_then(|f| f.pin.as_ref());
+                    outln!(
+                        "  {} {} {}",
+                        Colors::info(name),
+                        Colors::muted(current),
+                        Colors::warning(&format!(
+                            "({}, {} available)",
+                            pin.map(|p| p.to_string()).unwrap_or_default(),
+                            check.latest.as_deref().unwrap_or("unknown")
+                        ))
+                    );
+                }
+                UpdateStatus::Available => {
+                    outln!(
                         "  {} {} → {}",
                         Colors::info(name),
                         Colors::muted(current),
-                        Colors::success(latest.as_deref().unwrap_or("unknown"))
+                        Colors::success(check.latest.as_deref().unwrap_or("unknown"))
                     );
-                    updates.push((name.clone(), fact.installer.clone(), latest));
-                } else {
-                    println!(
+                    updates.push((name.clone(), check.installer.clone()));
+                }
+                UpdateStatus::UpToDate => outln!(
+                    "  {} {} {}",
+                    Colors::info(name),
+                    Colors::muted(current),
+                    Colors::muted("(up to date)")
+                ),
+                UpdateStatus::CheckFailed => {
+                    outln!(
                         "  {} {} {}",
                         Colors::info(name),
                         Colors::muted(current),
-                        Colors::muted("(up to date)")
+                        Colors::warning(&format!(
+                            "(could not check: {})",
+                            check.error.as_deref().unwrap_or("unknown error")
+                        ))
                     );
+                    unchecked.push(name.as_str());
                 }
             }
         }
 
+        if !unchecked.is_empty() {
+            outln!(
+                "\n{} Could not check {} for updates",
+                WARNING,
+                unchecked.join(", ")
+            );
+        }
+
         if updates.is_empty() {
-            println!("\n{} All tools are up to date!", SUCCESS);
+            if unchecked.is_empty() {
+                outln!("\n{} All tools are up to date!", SUCCESS);
+            } else {
+                outln!("\n{} No updates found for the other tools", INFO);
+            }
             return Ok(());
         }
 
         // Show summary of updates
-        println!(
+        outln!(
             "\n{} {} {} available",
             INFO,
             updates.len(),
@@ -321,277 +755,2327 @@ impl Forge {
 
         // Update package managers first (unless --tools-only)
         if !tools_only {
-            println!("\n{} Updating package managers...", ACTION);
+            outln!("\n{} Updating package managers...", ACTION);
+
+            // Find unique installers used by installed tools
+            let mut installers_to_update = std::collections::HashSet::new();
+            for fact in facts.tools.values() {
+                installers_to_update.insert(&fact.installer);
+            }
+
+            // Update each installer's provider
+            for installer_name in installers_to_update {
+                if let Some(provider_tool) = self.find_tool_that_provides(installer_name)
+                    && let Some(installer) = self.knowledge.installers.get(installer_name)
+                    && installer.update.is_some()
+                {
+                    outln!(
+                        "  {} Updating {} (provides {})",
+                        ACTION,
+                        Colors::info(&provider_tool.0),
+                        installer_name
+                    );
+
+                    // Execute the update
+                    if let Err(e) = self
+                        .execute_installer_update(&provider_tool.0, installer_name)
+                        .await
+                    {
+                        outln!("  {} Failed to update {}: {}", WARNING, provider_tool.0, e);
+                    }
+                }
+            }
+        }
+
+        // Perform updates
+        let installers: BTreeMap<String, String> = updates.iter().cloned().collect();
+        let results = self
+            .run_jobs(updates, move |forge, tool_name| {
+                let installer_name = installers[&tool_name].clone();
+                async move {
+                    outln!("\n{} Updating {}...", ACTION, Colors::info(&tool_name));
+                    forge.update_tool(&tool_name, &installer_name).await
+                }
+            })
+            .await;
+
+        let mut failed = Vec::new();
+        for (tool_name, result) in results {
+            if let Err(e) = result {
+                outln!("{} {}: {}", ERROR, Colors::error(&tool_name), e);
+                failed.push(tool_name);
+            }
+        }
+        if !failed.is_empty() {
+            anyhow::bail!("Failed to update {}", failed.join(", "));
+        }
+
+        if self.dry_run {
+            outln!("\n{} Dry run complete, nothing was changed", DRY_RUN);
+            return Ok(());
+        }
+
+        outln!("\n{} Updates complete!", SUCCESS);
+        Ok(())
+    }
+
+    /// Update one tool in place when its installer can, otherwise reinstall it
+    async fn update_tool(&self, tool_name: &str, installer_name: &str) -> Result<()> {
+        let tool = self
+            .knowledge
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool_name))?;
+        let tool_installer = tool.installers.get(installer_name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} doesn't support installer: {}",
+                tool_name,
+                installer_name
+            )
+        })?;
+        let installer = self
+            .knowledge
+            .installers
+            .get(installer_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown installer: {}", installer_name))?;
+
+        let update_script = get_platform_scripts(tool_installer, &self.platform)
+            .and_then(|scripts| scripts.update.as_deref());
+        let in_place = if installer.installer_type == "script" {
+            update_script.is_some()
+        } else {
+            installer.update.is_some()
+        };
+        if !in_place {
+            return self.reinstall_for_update(tool_name, installer_name).await;
+        }
+
+        if self.dry_run {
+            match update_script.filter(|_| installer.installer_type == "script") {
+                Some(script) => {
+                    outln!("{} Would run update script:", DRY_RUN);
+                    for line in self.platform.expand_pattern(script).trim().lines() {
+                        outln!("  {}", Colors::muted(line));
+                    }
+                }
+                None => {
+                    let command = expand_command(
+                        installer.update.as_deref().unwrap_or_default(),
+                        tool_name,
+                        tool_installer,
+                        None,
+                        &self.platform,
+                    );
+                    outln!("{} Would run: {}", DRY_RUN, command.join(" "));
+                }
+            }
+            outln!(
+                "{} Would record the new version of {} in ~/.forge/facts.toml",
+                DRY_RUN,
+                tool_name
+            );
+            return Ok(());
+        }
+
+        let previous = Facts::load_from(&self.paths)
+            .await?
+            .tools
+            .get(tool_name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("{} is not installed", tool_name))?;
+
+        // Package managers keep their own files; scripts overwrite ours
+        let mut backup = Backup::new(&self.paths.backup_dir());
+        let result = match update_script.filter(|_| installer.installer_type == "script") {
+            Some(script) => {
+                self.back_up_executables(tool_name, &previous, &mut backup)?;
+                crate::backend::execute_script_update(
+                    script,
+                    tool_name,
+                    &self.platform,
+                    tool,
+                    self.paths.bin_dir(),
+                    self.runner.as_ref(),
+                )
+            }
+            None => crate::backend::execute_update_with_runner(
+                installer,
+                tool_name,
+                tool_installer,
+                &self.platform,
+                self.runner.as_ref(),
+            ),
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                backup.restore()?;
+                return Err(e);
+            }
+        };
+
+        let mut fact = previous.clone();
+        let executable_names = match (&result.executables, &fact.executables) {
+            (Some(names), _) | (None, Some(names)) => names.clone(),
+            (None, None) if !tool.provides.is_empty() => tool.provides.clone(),
+            (None, None) => vec![tool_name.to_string()],
+        };
+        fact.installed_at = Utc::now();
+        fact.version = Some(result.version.clone());
+        if result.executables.is_some() {
+            fact.executables = result.executables;
+        }
+        fact.provenance = Some(Provenance {
+            executables: self.record_executables(&executable_names),
+            ..result.provenance
+        });
+        self.edit_facts(|facts| facts.tools.insert(tool_name.to_string(), fact))
+            .await?;
+
+        if previous.version.as_deref() != Some(result.version.as_str()) {
+            History::record(&self.paths, tool_name, &previous, backup.saved())?;
+        }
+        backup.discard();
+
+        outln!(
+            "{} {} updated to v{}",
+            SUCCESS,
+            Colors::success(tool_name),
+            Colors::warning(&result.version)
+        );
+        Ok(())
+    }
+
+    /// Reinstall a tool that has no way to update in place, keeping copies
+    /// of its executables until the new install succeeds
+    async fn reinstall_for_update(&self, tool_name: &str, installer_name: &str) -> Result<()> {
+        let facts = Facts::load_from(&self.paths).await?;
+        let fact = facts
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("{} is not installed", tool_name))?;
+
+        let result = self
+            .install_transaction(tool_name, Some(installer_name), None, None, true, true)
+            .await;
+        if result.is_err() {
+            outln!(
+                "{} Update failed, restored {} v{}",
+                WARNING,
+                tool_name,
+                fact.version.as_deref().unwrap_or("unknown")
+            );
+        }
+        result
+    }
+
+    /// Go back to the version a tool was at before its last install or update
+    pub async fn rollback(&self, tool_name: &str) -> Result<()> {
+        let facts = Facts::load_from(&self.paths).await?;
+        let current = facts
+            .tools
+            .get(tool_name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("{} is not installed", tool_name))?;
+
+        let mut history = History::load(&self.paths, tool_name)?;
+        let entry = history.latest().cloned().ok_or_else(|| {
+            anyhow::anyhow!("No earlier version of {} to roll back to", tool_name)
+        })?;
+        let previous_version = entry.fact.version.as_deref().unwrap_or("unknown");
+
+        outln!(
+            "{} Rolling back {} from v{} to v{} ({})",
+            ACTION,
+            Colors::info(tool_name),
+            current.version.as_deref().unwrap_or("unknown"),
+            Colors::success(previous_version),
+            entry.fact.installer
+        );
+
+        if entry.saved.is_empty() {
+            // Only the version was kept, so ask the installer for it again
+            let version = entry.fact.version.as_deref().ok_or_else(|| {
+                anyhow::anyhow!("The earlier {} install has no known version", tool_name)
+            })?;
+            self.install_transaction(
+                tool_name,
+                Some(&entry.fact.installer),
+                Some(version),
+                None,
+                false,
+                false,
+            )
+            .await?;
+        } else {
+            if self.dry_run {
+                for installed in entry.saved.keys() {
+                    outln!(
+                        "{} Would restore {} from ~/.forge/history",
+                        DRY_RUN,
+                        installed.display()
+                    );
+                }
+                outln!(
+                    "{} Would record {} v{} in ~/.forge/facts.toml",
+                    DRY_RUN,
+                    tool_name,
+                    previous_version
+                );
+                return Ok(());
+            }
+
+            let mut backup = Backup::new(&self.paths.backup_dir());
+            let restored = entry.saved.iter().try_for_each(|(installed, kept)| {
+                backup.save(installed)?;
+                crate::backup::copy_into_place(kept, installed)
+            });
+            if let Err(e) = restored {
+                backup.restore()?;
+                return Err(e);
+            }
+
+            let fact = ToolFact {
+                pin: current.pin.clone(),
+                ..entry.fact.clone()
+            };
+            self.edit_facts(|facts| facts.tools.insert(tool_name.to_string(), fact))
+                .await?;
+            backup.discard();
+        }
+
+        if self.dry_run {
+            return Ok(());
+        }
+        history.pop();
+        history.save(&self.paths, tool_name)?;
+
+        outln!(
+            "{} {} is back at v{}",
+            SUCCESS,
+            Colors::success(tool_name),
+            previous_version
+        );
+        Ok(())
+    }
+
+    /// Keep the version an install or update just replaced, with the
+    /// executables `backup` saved from it, so it can be rolled back to
+    async fn remember_replaced(
+        &self,
+        tool_name: &str,
+        previous: &ToolFact,
+        backup: &Backup,
+    ) -> Result<()> {
+        let current = Facts::load_from(&self.paths).await?;
+        let replaced = current.tools.get(tool_name).is_some_and(|fact| {
+            fact.version != previous.version || fact.installer != previous.installer
+        });
+        if replaced {
+            History::record(&self.paths, tool_name, previous, backup.saved())?;
+        }
+        Ok(())
+    }
+
+    /// Print a table of tools with newer versions, and of those that could
+    /// not be checked, without updating anything. Returns every check so the
+    /// caller can decide how to exit.
+    pub async fn outdated(&self, tool_name: Option<&str>) -> Result<Vec<UpdateCheck>> {
+        let checks = self.update_checks(tool_name).await?;
+        let behind: Vec<&UpdateCheck> = checks
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c.status,
+                    UpdateStatus::Available | UpdateStatus::Held | UpdateStatus::CheckFailed
+                )
+            })
+            .collect();
+
+        if behind.is_empty() {
+            outln!("{} All tools are up to date!", SUCCESS);
+            return Ok(checks);
+        }
+
+        let version = |v: &Option<String>| v.as_deref().unwrap_or("unknown").to_string();
+        let rows: Vec<[String; 5]> = behind
+            .iter()
+            .map(|c| {
+                let status = match c.status {
+                    UpdateStatus::Held => "pinned",
+                    UpdateStatus::CheckFailed => "check failed",
+                    _ => "available",
+                };
+                [
+                    c.name.clone(),
+                    c.installer.clone(),
+                    version(&c.current),
+                    version(&c.latest),
+                    status.to_string(),
+                ]
+            })
+            .collect();
+        let header = ["Tool", "Installer", "Current", "Latest", "Status"];
+        let widths: Vec<usize> = (0..header.len())
+            .map(|i| {
+                rows.iter()
+                    .map(|row| row[i].len())
+                    .chain([header[i].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let line = |cells: [&str; 5]| {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+        outln!("{}", Colors::muted(&line(header)));
+        for row in &rows {
+            let text = line([&row[0], &row[1], &row[2], &row[3], &row[4]]);
+            if row[4] == "available" {
+                outln!("{}", text);
+            } else if row[4] == "check failed" {
+                outln!("{}", Colors::warning(&text));
+            } else {
+                outln!("{}", Colors::muted(&text));
+            }
+        }
+
+        for check in behind
+            .iter()
+            .filter(|c| c.status == UpdateStatus::CheckFailed)
+        {
+            outln!(
+                "\n{} Could not check {}: {}",
+                WARNING,
+                check.name,
+                check.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+
+        let available = behind
+            .iter()
+            .filter(|c| c.status == UpdateStatus::Available)
+            .count();
+        if available > 0 {
+            outln!(
+                "\n{} {} {} available. Run {} to install",
+                INFO,
+                available,
+                if available == 1 { "update" } else { "updates" },
+                Colors::action("forge update")
+            );
+        }
+        Ok(checks)
+    }
+
+    /// Check one installed tool, or all of them in name order, for a newer
+    /// version without changing anything
+    pub async fn update_checks(&self, tool_name: Option<&str>) -> Result<Vec<UpdateCheck>> {
+        let facts = Facts::load_from(&self.paths).await?;
+        let mut tools: Vec<(String, ToolFact)> = match tool_name {
+            Some(name) => {
+                let fact = facts
+                    .tools
+                    .get(name)
+                    .ok_or_else(|| anyhow::anyhow!("{} is not installed", name))?;
+                vec![(name.to_string(), fact.clone())]
+            }
+            None => facts.tools.into_iter().collect(),
+        };
+        tools.sort_by(|a, b| a.0.cmp(&b.0));
+        self.check_updates(&tools).await
+    }
+
+    /// Look up the latest version of each tool, up to `jobs` at a time.
+    /// Results keep the order the tools were given in.
+    async fn check_updates(&self, tools: &[(String, ToolFact)]) -> Result<Vec<UpdateCheck>> {
+        let lookups = tools
+            .iter()
+            .filter(|(name, _)| self.knowledge.tools.contains_key(name))
+            .map(|(name, fact)| {
+                let forge = self.clone();
+                let (name, fact) = (name.clone(), fact.clone());
+                async move {
+                    if fact.pin.as_ref().is_some_and(|pin| pin.range.is_none()) {
+                        return Ok(UpdateCheck::new(&name, &fact, None));
+                    }
+                    // Each check is a subprocess or a blocking HTTP call. One
+                    // failed lookup shouldn't stop the others being checked.
+                    tokio::task::spawn_blocking(move || {
+                        Ok(match forge.latest_version(&name, &fact.installer) {
+                            Ok(latest) => UpdateCheck::new(&name, &fact, latest),
+                            Err(e) => UpdateCheck::failed(&name, &fact, &e),
+                        })
+                    })
+                    .await?
+                }
+            });
+
+        stream::iter(lookups)
+            .buffered(self.jobs)
+            .collect::<Vec<Result<UpdateCheck>>>()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    /// The newest version of a tool an installer offers, if it can tell
+    fn latest_version(&self, tool_name: &str, installer_name: &str) -> Result<Option<String>> {
+        let Some(tool) = self.knowledge.tools.get(tool_name) else {
+            return Ok(None);
+        };
+        let installer = self.knowledge.installers.get(installer_name);
+        let tool_installer = tool.installers.get(installer_name);
+        let package = tool_installer
+            .and_then(|ti| ti.package.as_deref())
+            .unwrap_or(tool_name);
+
+        if installer_name == "github"
+            && let Some(repo) = tool_installer.and_then(|ti| ti.repo.as_ref())
+        {
+            // Ask the Releases API directly rather than through gh
+            let release = self.github.latest_release(repo)?;
+            return Ok(Some(release.tag_name.trim_start_matches('v').to_string()));
+        }
+
+        match installer {
+            Some(installer) => check_latest_version(
+                installer_name,
+                package,
+                installer.version_check.as_ref(),
+                self.runner.as_ref(),
+            ),
+            None => Ok(None),
+        }
+    }
+
+    /// Run `job` for each tool, up to `jobs` at a time. Tools whose installer
+    /// takes a system-wide lock (apt, brew) get a lane of their own and go
+    /// one after another. Results keep the order the tools were given in.
+    async fn run_jobs<F, Fut>(
+        &self,
+        tools: Vec<(String, String)>,
+        job: F,
+    ) -> Vec<(String, Result<()>)>
+    where
+        F: Fn(Forge, String) -> Fut + Clone + Send + 'static,
+        Fut: Future<Output = Result<()>>,
+    {
+        // Previews read best in order
+        let all_serial = self.dry_run || self.jobs == 1;
+        let (serial, parallel): (Vec<_>, Vec<_>) =
+            tools
+                .into_iter()
+                .enumerate()
+                .partition(|(_, (_, installer))| {
+                    all_serial
+                        || self
+                            .knowledge
+                            .installers
+                            .get(installer)
+                            .is_some_and(|i| i.exclusive)
+                });
+
+        let spawn = |(index, (tool, _)): (usize, (String, String))| {
+            let forge = self.clone();
+            let job = job.clone();
+            async move {
+                // Installs block on subprocesses and downloads, so each
+                // gets a thread that drives it to completion. Side by side,
+                // a job's output is held back and printed when it is done.
+                let handle = tokio::runtime::Handle::current();
+                let name = tool.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    let run = || handle.block_on(job(forge, tool));
+                    if all_serial {
+                        return run();
+                    }
+                    let (result, output) = output::capture(run);
+                    print!("{}", output);
+                    result
+                })
+                .await
+                .unwrap_or_else(|e| Err(e.into()));
+                (index, name, result)
+            }
+        };
+
+        let serial = stream::iter(serial).then(&spawn).collect::<Vec<_>>();
+        let parallel = stream::iter(parallel)
+            .map(&spawn)
+            .buffer_unordered(self.jobs)
+            .collect::<Vec<_>>();
+        let (mut results, parallel) = futures::join!(serial, parallel);
+
+        results.extend(parallel);
+        results.sort_by_key(|(index, _, _)| *index);
+        results
+            .into_iter()
+            .map(|(_, name, result)| (name, result))
+            .collect()
+    }
+
+    /// The installer a tool would be installed with, without reporting why
+    /// others were passed over
+    fn resolve_installer(&self, tool_name: &str) -> Option<String> {
+        let tool = self.knowledge.tools.get(tool_name)?;
+        self.knowledge
+            .platforms
+            .get(&self.platform.os)?
+            .precedence
+            .iter()
+            .find(|name| {
+                tool.installers
+                    .get(*name)
+                    .is_some_and(|ti| self.probe_installer(name, ti).is_ok())
+            })
+            .cloned()
+    }
+
+    /// Load facts, change them and save them again without another job's
+    /// change landing in between
+    async fn edit_facts<T>(&self, edit: impl FnOnce(&mut Facts) -> T) -> Result<T> {
+        let _guard = self.facts_lock.lock().await;
+        let mut facts = Facts::load_from(&self.paths).await?;
+        let value = edit(&mut facts);
+        facts.save_to(&self.paths).await?;
+        Ok(value)
+    }
+
+    /// Save a tool's executables under the bin dir into `backup`
+    fn back_up_executables(
+        &self,
+        tool_name: &str,
+        fact: &ToolFact,
+        backup: &mut Backup,
+    ) -> Result<()> {
+        let executables =
+            self.installed_executables(tool_name, fact, self.knowledge.tools.get(tool_name));
+        for (_, path) in executables {
+            if let Some(path) = path.filter(|p| p.starts_with(self.paths.bin_dir())) {
+                backup.save(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a tool's executables from the bin dir, the part of an install
+    /// forge put there itself
+    fn remove_executables(&self, tool_name: &str, fact: &ToolFact) -> Result<()> {
+        let executables =
+            self.installed_executables(tool_name, fact, self.knowledge.tools.get(tool_name));
+        for (exe, path) in executables {
+            let Some(path) = path.filter(|p| p.starts_with(self.paths.bin_dir())) else {
+                continue;
+            };
+            if self.dry_run {
+                outln!("  {} Would remove {}", DRY_RUN, path.display());
+                continue;
+            }
+            outln!("  {} Removing executable: {}", ACTION, exe);
+            std::fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Run the uninstall command or script of the installer a tool came from
+    fn run_uninstaller(&self, tool_name: &str, fact: &ToolFact) -> Result<()> {
+        let Some(installer) = self.knowledge.installers.get(&fact.installer) else {
+            return Ok(());
+        };
+        if let Some(uninstall_cmd) = &installer.uninstall {
+            let default = Default::default();
+            let tool_config = self
+                .knowledge
+                .tools
+                .get(tool_name)
+                .and_then(|t| t.installers.get(&fact.installer))
+                .unwrap_or(&default);
+
+            let command =
+                expand_command(uninstall_cmd, tool_name, tool_config, None, &self.platform);
+
+            if self.dry_run {
+                outln!("{} Would run: {}", DRY_RUN, command.join(" "));
+            } else {
+                outln!("{} Running: {}", ACTION, Colors::muted(&command.join(" ")));
+                let output = self.runner.run(&command[0], &command[1..])?;
+
+                if !output.status.success() {
+                    outln!("{} Uninstall command failed", WARNING);
+                }
+            }
+        } else if installer.installer_type == "script" {
+            // Use platform-specific uninstall script if available
+            if let Some(tool_def) = self.knowledge.tools.get(tool_name)
+                && let Some(tool_installer) = tool_def.installers.get(&fact.installer)
+            {
+                let platform_scripts = match self.platform.os.as_str() {
+                    "linux" => &tool_installer.linux,
+                    "macos" => &tool_installer.macos,
+                    "windows" => &tool_installer.windows,
+                    _ => &None,
+                };
+
+                if let Some(scripts) = platform_scripts {
+                    if let Some(uninstall_script) = &scripts.uninstall {
+                        if self.dry_run {
+                            outln!("{} Would run uninstall script:", DRY_RUN);
+                            for line in uninstall_script.trim().lines() {
+                                outln!("  {}", Colors::muted(line));
+                            }
+                        } else {
+                            outln!("{} Running uninstall script...", ACTION);
+                            let output = self
+                                .runner
+                                .run("sh", &["-c".to_string(), uninstall_script.clone()])?;
+
+                            if !output.status.success() {
+                                outln!("{} Uninstall script failed", WARNING);
+                            }
+                        }
+                    } else {
+                        outln!("{} No uninstaller available for {}", INFO, tool_name);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a replaced install's uninstaller once its successor is in place,
+    /// putting back any of the new `installed` executables it takes with it
+    fn retire_installation(
+        &self,
+        tool_name: &str,
+        old: &ToolFact,
+        installed: &[PathBuf],
+    ) -> Result<()> {
+        let mut keep = Backup::new(&self.paths.backup_dir());
+        for path in installed {
+            keep.save(path)?;
+        }
+
+        outln!(
+            "{} Removing the {} install of {}...",
+            ACTION,
+            old.installer,
+            Colors::warning(tool_name)
+        );
+        self.run_uninstaller(tool_name, old)?;
+
+        for (path, copy) in keep.saved() {
+            if !path.exists() {
+                crate::backup::copy_into_place(copy, path)?;
+            }
+        }
+        keep.discard();
+        Ok(())
+    }
+
+    pub async fn uninstall(&self, tool_name: &str) -> Result<()> {
+        outln!(
+            "{} Preparing to uninstall {}...",
+            ACTION,
+            Colors::info(tool_name)
+        );
+
+        let facts = Facts::load_from(&self.paths).await?;
+
+        if let Some(fact) = facts.tools.get(tool_name) {
+            let tool = self.knowledge.tools.get(tool_name);
+            let provides: &[_] = tool.as_ref().map_or(&[], |t| &t.provides);
+
+            // Check if this tool provides any installers
+            if !provides.is_empty() {
+                // Find all tools installed by the installers this tool provides
+                let dependent_tools: Vec<String> = facts
+                    .tools
+                    .iter()
+                    .filter(|(name, f)| *name != tool_name && provides.contains(&f.installer))
+                    .map(|(name, _)| name.clone())
+                    .collect();
+
+                if !dependent_tools.is_empty() {
+                    outln!(
+                        "\n{} {} provides the {} installer",
+                        WARNING,
+                        tool_name,
+                        provides.join(", ")
+                    );
+                    outln!("The following tools were installed using it:");
+                    for dep in &dependent_tools {
+                        outln!("  • {}", Colors::info(dep));
+                    }
+                    outln!("\nThese tools will be removed from Forge's records.");
+                    outln!("(The actual binaries may also be removed by the uninstaller)");
+                }
+            }
+
+            // No confirmation needed - trust the user
+            outln!(
+                "\n{} Uninstalling {}...",
+                ACTION,
+                Colors::warning(tool_name)
+            );
+
+            self.remove_executables(tool_name, fact)?;
+            self.run_uninstaller(tool_name, fact)?;
+
+            if self.dry_run {
+                outln!(
+                    "{} Would remove {} from ~/.forge/facts.toml",
+                    DRY_RUN,
+                    tool_name
+                );
+                return Ok(());
+            }
+
+            // Remove from facts, along with tools that were installed by
+            // this tool's installers
+            self.edit_facts(|facts| {
+                facts.tools.remove(tool_name);
+                if !provides.is_empty() {
+                    let tools_to_remove: Vec<String> = facts
+                        .tools
+                        .iter()
+                        .filter(|(_, f)| provides.contains(&f.installer))
+                        .map(|(name, _)| name.clone())
+                        .collect();
+
+                    for tool in tools_to_remove {
+                        outln!("{} Removing {} from records", ACTION, Colors::muted(&tool));
+                        facts.tools.remove(&tool);
+                    }
+                }
+            })
+            .await?;
+
+            outln!("{} {} uninstalled", SUCCESS, Colors::success(tool_name));
+        } else {
+            outln!("{} {} is not installed", INFO, tool_name);
+        }
+
+        Ok(())
+    }
+
+    /// Print tools matching `query`, best first, marking which are installed
+    /// and which can be installed here
+    pub async fn search(&self, query: &str) -> Result<()> {
+        let results = self.search_tools(query).await?;
+        if results.is_empty() {
+            outln!("{} No tools match {}", INFO, Colors::info(query));
+            return Ok(());
+        }
+
+        outln!(
+            "{} {} {} matching {}",
+            SEARCH,
+            results.len(),
+            if results.len() == 1 { "tool" } else { "tools" },
+            Colors::info(query)
+        );
+
+        let statuses: Vec<String> = results
+            .iter()
+            .map(|result| match (&result.installed, result.installable) {
+                (Some(version), _) => format!("installed v{}", version),
+                (None, true) => "installable".to_string(),
+                (None, false) => format!("not available on {}", self.platform.os),
+            })
+            .collect();
+        let name_width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+        let status_width = statuses.iter().map(|s| s.len()).max().unwrap_or(0);
+
+        for (result, status) in results.iter().zip(&statuses) {
+            let status = match (&result.installed, result.installable) {
+                (Some(_), _) => Colors::success(status),
+                (None, true) => Colors::action(status),
+                (None, false) => Colors::muted(status),
+            };
+            outln!(
+                "  {:name_width$}  {:status_width$}  {}",
+                Colors::info(&result.name),
+                status,
+                Colors::muted(&result.description)
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Tools in the knowledge base matching `query`, best first
+    pub async fn search_tools(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let facts = Facts::load_from(&self.paths).await?;
+        Ok(crate::search::rank(&self.knowledge, query)
+            .into_iter()
+            .filter_map(|(name, score)| {
+                let tool = self.knowledge.tools.get(&name)?;
+                Some(SearchResult {
+                    description: tool.description.clone(),
+                    score,
+                    installable: !self.platform_installers(tool).is_empty(),
+                    installed: facts
+                        .tools
+                        .get(&name)
+                        .map(|fact| fact.version.clone().unwrap_or_else(|| "unknown".into())),
+                    name,
+                })
+            })
+            .collect())
+    }
+
+    pub fn why(&self, tool_name: &str) -> Result<()> {
+        let tool = self
+            .knowledge
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool_name))?;
+
+        outln!("{}", Colors::info(tool_name));
+        outln!("{}", Colors::muted(&tool.description));
+
+        Ok(())
+    }
+
+    /// Show which tools provide an executable, which of them is installed and
+    /// which binary on PATH actually runs
+    pub async fn which(&self, executable: &str) -> Result<()> {
+        let location = self.locate(executable).await?;
+
+        outln!("{} {}", SEARCH, Colors::info(executable));
+
+        if location.providers.is_empty() {
+            outln!(
+                "  {}",
+                Colors::muted("No tool in the knowledge base provides it")
+            );
+        } else {
+            outln!("  Provided by:");
+            let width = location
+                .providers
+                .iter()
+                .map(|p| p.name.len())
+                .max()
+                .unwrap_or(0);
+            for provider in &location.providers {
+                let Some(version) = &provider.installed else {
+                    outln!(
+                        "    • {:width$}  {}",
+                        Colors::info(&provider.name),
+                        Colors::muted("not installed")
+                    );
+                    continue;
+                };
+                outln!(
+                    "    • {:width$}  {} v{} via {}{}",
+                    Colors::info(&provider.name),
+                    Colors::success("installed"),
+                    version,
+                    provider.installer.as_deref().unwrap_or("unknown"),
+                    provider
+                        .path
+                        .as_ref()
+                        .map(|p| format!(" → {}", p.display()))
+                        .unwrap_or_default()
+                );
+            }
+        }
+
+        let off_path: Vec<(&String, &PathBuf)> = location
+            .providers
+            .iter()
+            .filter_map(|p| Some((&p.name, p.path.as_ref()?)))
+            .filter(|(_, path)| !location.on_path.iter().any(|p| same_file(p, path)))
+            .collect();
+
+        let Some(winner) = location.on_path.first() else {
+            outln!("\n{} {} is not on PATH", WARNING, executable);
+            if let Some((name, path)) = off_path.first() {
+                outln!(
+                    "{} {} installed it at {}. Add its directory to PATH: {}",
+                    TIP,
+                    name,
+                    path.display(),
+                    Colors::action(&crate::doctor::path_fix(
+                        path.parent().unwrap_or(path),
+                        &self.platform.os
+                    ))
+                );
+            } else if let Some(provider) = location.providers.iter().find(|p| p.installable) {
+                outln!(
+                    "{} Install it with: {}",
+                    TIP,
+                    Colors::action(&format!("forge install {}", provider.name))
+                );
+            } else {
+                outln!(
+                    "{} Look for it with: {}",
+                    TIP,
+                    Colors::action(&format!("forge search {}", executable))
+                );
+            }
+            return Ok(());
+        };
+
+        let source = match &location.owner {
+            Some(name) => format!("from {}", name),
+            None => "not installed by forge".to_string(),
+        };
+        outln!(
+            "\n  Runs: {} {}",
+            Colors::success(&winner.display().to_string()),
+            Colors::muted(&format!("({})", source))
+        );
+        for other in location.on_path.iter().skip(1) {
+            outln!(
+                "  {}",
+                Colors::muted(&format!("shadows {}", other.display()))
+            );
+        }
+        for (name, path) in off_path {
+            outln!("  {} {}'s {} is not on PATH", WARNING, name, path.display());
+        }
+
+        Ok(())
+    }
+
+    /// Find the tools that provide an executable and every copy of it on PATH
+    pub async fn locate(&self, executable: &str) -> Result<Location> {
+        let facts = Facts::load_from(&self.paths).await?;
+        let search_path = self.paths.search_path();
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let on_path: Vec<PathBuf> = which::which_in_all(executable, Some(&search_path), cwd)
+            .map(|found| found.collect())
+            .unwrap_or_default();
+
+        let providers: Vec<Provider> = self
+            .tools_that_provide(executable)
+            .into_iter()
+            .map(|(name, tool)| {
+                let fact = facts.tools.get(&name);
+                let path = fact.and_then(|fact| {
+                    self.installed_executables(&name, fact, Some(tool))
+                        .into_iter()
+                        .find(|(exe, _)| exe == executable)
+                        .and_then(|(_, path)| path)
+                });
+                Provider {
+                    installed: fact.map(|f| f.version.clone().unwrap_or_else(|| "unknown".into())),
+                    installer: fact.map(|f| f.installer.clone()),
+                    path,
+                    installable: !self.platform_installers(tool).is_empty(),
+                    name,
+                }
+            })
+            .collect();
+
+        let owner = on_path.first().and_then(|winner| {
+            providers
+                .iter()
+                .find(|p| p.path.as_ref().is_some_and(|path| same_file(path, winner)))
+                .map(|p| p.name.clone())
+        });
+
+        Ok(Location {
+            providers,
+            on_path,
+            owner,
+        })
+    }
+
+    /// Print everything known about a tool: where its definition comes from,
+    /// how it can be installed here, what is installed and what is latest
+    pub async fn info(&self, tool_name: &str) -> Result<()> {
+        let tool = self
+            .knowledge
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool_name))?;
+        let facts = Facts::load_from(&self.paths).await?;
+        let fact = facts.tools.get(tool_name);
+        let usable = self.platform_installers(tool);
+
+        let source = if self.knowledge.local_tools.contains(tool_name) {
+            "local overlay"
+        } else {
+            "bundled"
+        };
+        outln!(
+            "{} {}",
+            Colors::info(tool_name),
+            Colors::muted(&format!("({})", source))
+        );
+        outln!("{}", Colors::muted(&tool.description));
+        outln!();
+
+        let label = |name: &str| format!("{:12}", format!("{}:", name));
+        if !tool.provides.is_empty() {
+            outln!("  {}{}", label("Provides"), tool.provides.join(", "));
+        }
+
+        // Usable installers in precedence order, then the rest by name
+        let mut others: Vec<&String> = tool
+            .installers
+            .keys()
+            .filter(|name| !usable.contains(name))
+            .collect();
+        others.sort();
+        let mut installers: Vec<String> = usable
+            .iter()
+            .map(|name| Colors::success(name).to_string())
+            .collect();
+        installers.extend(others.into_iter().map(|name| {
+            Colors::muted(&format!("{} (not on {})", name, self.platform.os)).to_string()
+        }));
+        outln!("  {}{}", label("Installers"), installers.join(", "));
+
+        match fact {
+            Some(fact) => {
+                let pin = fact
+                    .pin
+                    .as_ref()
+                    .map(|pin| format!(" ({})", pin))
+                    .unwrap_or_default();
+                outln!(
+                    "  {}v{} via {} on {}{}",
+                    label("Installed"),
+                    fact.version.as_deref().unwrap_or("unknown"),
+                    Colors::info(&fact.installer),
+                    fact.installed_at.format("%Y-%m-%d %H:%M UTC"),
+                    Colors::warning(&pin)
+                );
+            }
+            None => outln!("  {}{}", label("Installed"), Colors::muted("no")),
+        }
+
+        // Ask the installer in use, or the one install would try first
+        let installer = fact
+            .map(|f| f.installer.clone())
+            .or(usable.first().cloned());
+        if let Some(installer) = installer {
+            let latest = match self.latest_version(tool_name, &installer) {
+                Ok(Some(version)) => format!("v{} ({})", version, installer),
+                Ok(None) => format!("unknown ({} can't tell)", installer),
+                Err(e) => format!("unknown ({})", e),
+            };
+            outln!("  {}{}", label("Latest"), latest);
+        }
+
+        Ok(())
+    }
+
+    /// What the knowledge base and facts say about a tool
+    pub async fn tool_info(&self, tool_name: &str) -> Result<ToolInfo> {
+        let tool = self
+            .knowledge
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool_name))?;
+        let facts = Facts::load_from(&self.paths).await?;
+
+        let mut installers: Vec<String> = tool.installers.keys().cloned().collect();
+        installers.sort();
+        Ok(ToolInfo {
+            name: tool_name.to_string(),
+            description: tool.description.clone(),
+            provides: tool.provides.clone(),
+            installers,
+            local: self.knowledge.local_tools.contains(tool_name),
+            installed: facts.tools.get(tool_name).map(Installation::from),
+        })
+    }
+
+    /// Every installed tool, in name order
+    pub async fn installed_tools(&self) -> Result<ToolList> {
+        let facts = Facts::load_from(&self.paths).await?;
+        let mut tools: Vec<InstalledTool> = facts
+            .tools
+            .iter()
+            .map(|(name, fact)| InstalledTool {
+                name: name.clone(),
+                description: self
+                    .knowledge
+                    .tools
+                    .get(name)
+                    .map(|t| t.description.clone()),
+                local: self.knowledge.local_tools.contains(name),
+                installation: Installation::from(fact),
+            })
+            .collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(ToolList { tools })
+    }
+
+    pub async fn plan(&self, tool_spec: &str) -> Result<()> {
+        let plan = self.install_plan(tool_spec).await?;
+
+        outln!(
+            "{} Installer resolution for {} on {} ({})",
+            SEARCH,
+            Colors::info(&plan.name),
+            plan.os,
+            plan.arch
+        );
+        outln!(
+            "   Precedence: {}",
+            Colors::muted(&plan.precedence.join(" → "))
+        );
+
+        if let Some(installed) = &plan.installed {
+            outln!(
+                "   Installed via {} (v{})",
+                Colors::info(&installed.installer),
+                Colors::muted(installed.version.as_deref().unwrap_or("unknown"))
+            );
+        }
+        outln!();
+
+        let width = plan
+            .candidates
+            .iter()
+            .map(|c| c.installer.len())
+            .max()
+            .unwrap_or(0);
+
+        for candidate in &plan.candidates {
+            let name = &candidate.installer;
+            match candidate.status {
+                CandidateStatus::Selected => outln!(
+                    "  {:width$}  {} {}",
+                    Colors::action(name),
+                    Colors::success("available"),
+                    Colors::success("← selected")
+                ),
+                CandidateStatus::Available => {
+                    outln!("  {:width$}  {}", name, Colors::success("available"))
+                }
+                CandidateStatus::Unavailable => outln!(
+                    "  {:width$}  {}",
+                    name,
+                    Colors::warning(&format!(
+                        "unavailable: {}",
+                        candidate.reason.as_deref().unwrap_or_default()
+                    ))
+                ),
+                CandidateStatus::NotDeclared => {
+                    outln!("  {:width$}  {}", name, Colors::muted("not declared"))
+                }
+                CandidateStatus::NotUsed => outln!(
+                    "  {:width$}  {}",
+                    name,
+                    Colors::muted(&format!("not used on {}", plan.os))
+                ),
+            }
+        }
+
+        let (Some(installer_key), Some(action)) = (&plan.selected, &plan.action) else {
+            outln!(
+                "\n{} No usable installer for {} on {}",
+                WARNING,
+                plan.name,
+                plan.os
+            );
+            let first_failure = plan
+                .candidates
+                .iter()
+                .find(|c| c.status == CandidateStatus::Unavailable);
+            if let Some(provider) =
+                first_failure.and_then(|c| self.find_tool_that_provides(&c.installer))
+            {
+                outln!(
+                    "{} Install one with: {}",
+                    crate::color::TIP,
+                    Colors::action(&format!("forge install {}", provider.0))
+                );
+            }
+            return Ok(());
+        };
+
+        outln!(
+            "\n{} forge install {} would use {}:",
+            ACTION,
+            tool_spec,
+            Colors::action(installer_key)
+        );
+
+        match action {
+            PlannedAction::Command { command } => {
+                outln!("  {}", command.join(" "));
+            }
+            PlannedAction::Script { script } => {
+                for line in script.trim().lines() {
+                    outln!("  {}", Colors::muted(line));
+                }
+            }
+            PlannedAction::GithubRelease {
+                repo,
+                pattern,
+                version,
+            } => match pattern {
+                Some(pattern) => outln!(
+                    "  Download assets matching {} from {}",
+                    Colors::info(pattern),
+                    release_label(repo, version.as_deref())
+                ),
+                None => outln!(
+                    "  Download the best-matching asset from {}",
+                    release_label(repo, version.as_deref())
+                ),
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Walk the platform precedence exactly as install would, recording why
+    /// each installer was or wasn't chosen and what the chosen one would run
+    pub async fn install_plan(&self, tool_spec: &str) -> Result<PlanReport> {
+        let (tool_name, version) = split_version(tool_spec);
+        let tool = self
+            .knowledge
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool_name))?;
+
+        let platform_name = &self.platform.os;
+        let precedence = self
+            .knowledge
+            .platforms
+            .get(platform_name)
+            .map(|p| &p.precedence)
+            .ok_or_else(|| anyhow::anyhow!("No platform config for {}", platform_name))?;
+
+        let facts = Facts::load_from(&self.paths).await?;
+
+        let mut candidates = Vec::new();
+        let mut selected = None;
+        for installer_name in precedence {
+            let (status, reason) = match tool.installers.get(installer_name) {
+                None => (CandidateStatus::NotDeclared, None),
+                Some(tool_installer) => {
+                    match self.probe_installer(installer_name, tool_installer) {
+                        Ok(()) if selected.is_none() => {
+                            selected = Some((installer_name, tool_installer));
+                            (CandidateStatus::Selected, None)
+                        }
+                        Ok(()) => (CandidateStatus::Available, None),
+                        Err(reason) => (CandidateStatus::Unavailable, Some(reason)),
+                    }
+                }
+            };
+            candidates.push(Candidate {
+                installer: installer_name.clone(),
+                status,
+                reason,
+            });
+        }
+
+        // Installers the tool declares that this platform never considers
+        let mut ignored: Vec<&String> = tool
+            .installers
+            .keys()
+            .filter(|name| !precedence.contains(name))
+            .collect();
+        ignored.sort();
+        candidates.extend(ignored.into_iter().map(|name| Candidate {
+            installer: name.clone(),
+            status: CandidateStatus::NotUsed,
+            reason: None,
+        }));
+
+        let action = match selected {
+            Some((installer_key, tool_installer)) => {
+                let installer = self
+                    .knowledge
+                    .installers
+                    .get(installer_key)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown installer: {}", installer_key))?;
+                Some(
+                    match plan_install(
+                        installer_key,
+                        installer,
+                        tool_name,
+                        tool_installer,
+                        version,
+                        &self.platform,
+                    )? {
+                        InstallPlan::Command(command) => PlannedAction::Command { command },
+                        InstallPlan::Script(script) => PlannedAction::Script { script },
+                        InstallPlan::GithubRelease { repo, pattern } => {
+                            PlannedAction::GithubRelease {
+                                repo,
+                                pattern,
+                                version: version.map(str::to_string),
+                            }
+                        }
+                    },
+                )
+            }
+            None => None,
+        };
+
+        Ok(PlanReport {
+            name: tool_name.to_string(),
+            version: version.map(str::to_string),
+            os: platform_name.clone(),
+            arch: self.platform.arch.clone(),
+            precedence: precedence.clone(),
+            installed: facts.tools.get(tool_name).map(Installation::from),
+            candidates,
+            selected: selected.map(|(name, _)| name.clone()),
+            action,
+        })
+    }
+
+    /// Print what installing a tool with the given installer would do
+    fn preview_install(
+        &self,
+        tool_name: &str,
+        installer_key: &str,
+        version: Option<&str>,
+    ) -> Result<()> {
+        let tool = self
+            .knowledge
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool_name))?;
+        let tool_installer = tool.installers.get(installer_key).ok_or_else(|| {
+            anyhow::anyhow!("{} doesn't support installer: {}", tool_name, installer_key)
+        })?;
+        let installer = self
+            .knowledge
+            .installers
+            .get(installer_key)
+            .ok_or_else(|| anyhow::anyhow!("Unknown installer: {}", installer_key))?;
+
+        match plan_install(
+            installer_key,
+            installer,
+            tool_name,
+            tool_installer,
+            version,
+            &self.platform,
+        )? {
+            InstallPlan::Command(command) => {
+                outln!("{} Would run: {}", DRY_RUN, command.join(" "));
+            }
+            InstallPlan::Script(script) => {
+                outln!("{} Would run script:", DRY_RUN);
+                for line in script.trim().lines() {
+                    outln!("  {}", Colors::muted(line));
+                }
+            }
+            InstallPlan::GithubRelease {
+                repo,
+                pattern: Some(pattern),
+            } => {
+                outln!(
+                    "{} Would download assets matching {} from {} into {}",
+                    DRY_RUN,
+                    pattern,
+                    release_label(&repo, version),
+                    self.paths.bin_dir().display()
+                );
+            }
+            InstallPlan::GithubRelease {
+                repo,
+                pattern: None,
+            } => {
+                // Discovery only reads release metadata
+                let discovery = discover_asset(
+                    &repo,
+                    &self.platform.os,
+                    &self.platform.arch,
+                    version,
+                    &self.github,
+                )?;
+                outln!("{} Would download: {}", DRY_RUN, discovery.download_url);
+                if tool.provides.is_empty() {
+                    outln!(
+                        "{} Would install executables from {} into ~/.local/bin",
+                        DRY_RUN,
+                        discovery.asset_name
+                    );
+                } else {
+                    outln!(
+                        "{} Would install {} from {} into ~/.local/bin",
+                        DRY_RUN,
+                        tool.provides.join(", "),
+                        discovery.asset_name
+                    );
+                }
+            }
+        }
+
+        outln!(
+            "{} Would record {} in ~/.forge/facts.toml",
+            DRY_RUN,
+            tool_name
+        );
+        Ok(())
+    }
+
+    /// Hold an installed tool back from `forge update`, or keep it within
+    /// a version prefix such as `1.28`
+    pub async fn pin(&self, tool_name: &str, range: Option<&str>) -> Result<()> {
+        let facts = Facts::load_from(&self.paths).await?;
+        let fact = facts
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("{} is not installed", tool_name))?;
+
+        let pin = Pin {
+            range: range.map(str::to_string),
+        };
+        if let (Some(range), Some(version)) = (range, &fact.version)
+            && !pin.allows(version)
+        {
+            outln!(
+                "{} {} v{} is outside {}",
+                WARNING,
+                tool_name,
+                version,
+                Colors::warning(range)
+            );
+        }
+
+        let version = fact.version.clone().unwrap_or_else(|| "unknown".into());
+        if self.dry_run {
+            outln!(
+                "{} Would record {} v{} as {} in ~/.forge/facts.toml",
+                DRY_RUN,
+                tool_name,
+                version,
+                pin
+            );
+            return Ok(());
+        }
+
+        self.set_pin(tool_name, Some(pin.clone())).await?;
+        outln!(
+            "{} {} v{} is now {}",
+            SUCCESS,
+            Colors::success(tool_name),
+            version,
+            pin
+        );
+        Ok(())
+    }
+
+    pub async fn unpin(&self, tool_name: &str) -> Result<()> {
+        let facts = Facts::load_from(&self.paths).await?;
+        let fact = facts
+            .tools
+            .get(tool_name)
+            .ok_or_else(|| anyhow::anyhow!("{} is not installed", tool_name))?;
+
+        if fact.pin.is_none() {
+            outln!("{} {} is not pinned", INFO, tool_name);
+            return Ok(());
+        }
+        if self.dry_run {
+            outln!(
+                "{} Would remove the pin on {} from ~/.forge/facts.toml",
+                DRY_RUN,
+                tool_name
+            );
+            return Ok(());
+        }
+
+        self.set_pin(tool_name, None).await?;
+        outln!(
+            "{} {} will be updated again",
+            SUCCESS,
+            Colors::success(tool_name)
+        );
+        Ok(())
+    }
+
+    /// Record a tool's pin, or clear it with `None`
+    async fn set_pin(&self, tool_name: &str, pin: Option<Pin>) -> Result<()> {
+        self.edit_facts(|facts| facts.tools.get_mut(tool_name).map(|fact| fact.pin = pin))
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("{} is not installed", tool_name))
+    }
+
+    pub async fn list(&self, verbose: bool) -> Result<()> {
+        let facts = Facts::load_from(&self.paths).await?;
+
+        if facts.tools.is_empty() {
+            outln!("{}", Colors::muted("No tools installed yet."));
+            outln!("\nGet started with:");
+            outln!("  {}", Colors::action("forge install ripgrep"));
+            return Ok(());
+        }
+
+        // Calculate column widths
+        let mut max_name_len = 0;
+        let mut max_version_len = 0;
+        let mut max_installer_len = 0;
+
+        for (name, fact) in &facts.tools {
+            max_name_len = max_name_len.max(name.len());
+            let version_len = fact.version.as_deref().unwrap_or("unknown").len() + 1; // +1 for 'v' prefix
+            max_version_len = max_version_len.max(version_len);
+            max_installer_len = max_installer_len.max(fact.installer.len());
+        }
+
+        outln!("Installed tools:");
+        for (name, fact) in &facts.tools {
+            let tool = self.knowledge.tools.get(name);
+            let description = tool
+                .map(|t| t.description.as_str())
+                .unwrap_or("Unknown tool");
+            let version = fact.version.as_deref().unwrap_or("unknown");
+
+            // Add (local) marker if from local overlay
+            let local_marker = if self.knowledge.local_tools.contains(name) {
+                " (local)"
+            } else {
+                ""
+            };
+
+            let pin_marker = fact
+                .pin
+                .as_ref()
+                .map(|pin| format!(" ({})", pin))
+                .unwrap_or_default();
+
+            outln!(
+                "  • {}{} {}{} - {}",
+                Colors::info(name),
+                Colors::muted(local_marker),
+                Colors::muted(&format!("v{}", version)),
+                Colors::warning(&pin_marker),
+                Colors::muted(description)
+            );
+
+            if verbose {
+                print_provenance(fact);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rehash forge-managed executables and report any drift since install
+    pub async fn verify(&self, tool_name: Option<&str>, restore: bool) -> Result<()> {
+        use crate::verify::{Integrity, check_executables};
+
+        let facts = Facts::load_from(&self.paths).await?;
+        if let Some(name) = tool_name
+            && !facts.tools.contains_key(name)
+        {
+            anyhow::bail!("{} is not installed", name);
+        }
+
+        let checks: Vec<_> = check_executables(&facts, self.paths.bin_dir())
+            .into_iter()
+            .filter(|c| tool_name.is_none_or(|name| c.tool == name))
+            .collect();
+
+        outln!(
+            "{} Verifying {} executables in {}",
+            SEARCH,
+            checks.len(),
+            Colors::muted(&self.paths.bin_dir().display().to_string())
+        );
+
+        let mut drifted: Vec<String> = Vec::new();
+        for check in &checks {
+            let status = match &check.integrity {
+                Integrity::Intact => continue,
+                Integrity::Modified => "modified".to_string(),
+                Integrity::Replaced { by } => format!("replaced by {}", by),
+                Integrity::Deleted => "deleted".to_string(),
+            };
+            outln!(
+                "  {} {} ({}) {}",
+                ERROR,
+                Colors::error(&check.path.display().to_string()),
+                check.tool,
+                Colors::warning(&status)
+            );
+            if !drifted.contains(&check.tool) {
+                drifted.push(check.tool.clone());
+            }
+        }
+
+        let unrecorded: Vec<&String> = facts
+            .tools
+            .iter()
+            .filter(|(name, fact)| {
+                tool_name.is_none_or(|t| t == name.as_str()) && fact.provenance.is_none()
+            })
+            .map(|(name, _)| name)
+            .collect();
+        if !unrecorded.is_empty() {
+            outln!(
+                "{} No hashes recorded for {}; reinstall to start verifying",
+                INFO,
+                Colors::muted(
+                    &unrecorded
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            );
+        }
+
+        let drift_count = checks.iter().filter(|c| c.integrity.is_drift()).count();
+        if drift_count == 0 {
+            outln!("{} All executables intact", SUCCESS);
+            return Ok(());
+        }
+
+        if !restore {
+            outln!(
+                "\n{} Run {} to reinstall the affected tools",
+                TIP,
+                Colors::action("forge verify --restore")
+            );
+            anyhow::bail!(
+                "{} of {} executables failed verification",
+                drift_count,
+                checks.len()
+            );
+        }
+
+        for name in &drifted {
+            let fact = &facts.tools[name];
+            outln!(
+                "\n{} Restoring {} via {}",
+                ACTION,
+                Colors::info(name),
+                Colors::action(&fact.installer)
+            );
+            self.reinstall(name, fact).await?;
+        }
+
+        if self.dry_run {
+            outln!("\n{} Dry run complete, nothing was changed", DRY_RUN);
+        } else {
+            outln!("\n{} Restored {}", SUCCESS, drifted.join(", "));
+        }
+        Ok(())
+    }
+
+    /// Install a tool again with the installer it was recorded with, keeping
+    /// the old fact if the install fails
+    async fn reinstall(&self, tool_name: &str, fact: &ToolFact) -> Result<()> {
+        // Put back the recorded version rather than whatever is newest now
+        let version = fact.version.as_deref().filter(|_| {
+            self.knowledge
+                .installers
+                .get(&fact.installer)
+                .is_some_and(|i| fact.installer == "github" || i.install_version.is_some())
+        });
+        if version.is_none() {
+            outln!(
+                "{} {} can't install a specific version, reinstalling the latest",
+                WARNING,
+                fact.installer
+            );
+        }
+
+        self.install_transaction(tool_name, Some(&fact.installer), version, None, false, true)
+            .await
+    }
+
+    /// Bring this machine in line with a project's `forge.project.toml`,
+    /// or with `check` only report whether it already is. With `locked`,
+    /// tools must be exactly what the adjacent `forge.lock` records.
+    pub async fn apply(
+        &self,
+        manifest_path: Option<&str>,
+        check: bool,
+        locked: bool,
+    ) -> Result<()> {
+        use crate::lock::{LOCK_FILE, Lockfile};
+        use crate::project::{PROJECT_FILE, ProjectManifest};
+
+        let manifest_path = match manifest_path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let cwd = std::env::current_dir()?;
+                ProjectManifest::find(&cwd).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No {} found in {} or its parents",
+                        PROJECT_FILE,
+                        cwd.display()
+                    )
+                })?
+            }
+        };
+        let manifest = ProjectManifest::load(&manifest_path)?;
+
+        outln!(
+            "{} Checking {}",
+            SEARCH,
+            Colors::muted(&manifest_path.display().to_string())
+        );
+
+        let unknown: Vec<&String> = manifest
+            .tools
+            .iter()
+            .filter(|t| !self.knowledge.tools.contains_key(*t))
+            .collect();
+        if !unknown.is_empty() {
+            anyhow::bail!(
+                "Unknown tools in {}: {}",
+                manifest_path.display(),
+                unknown
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+
+        let lock_path = manifest_path.with_file_name(LOCK_FILE);
+        let mut lockfile = Lockfile::load(&lock_path)?;
+        let triple = self.platform.target_triple();
+
+        if locked {
+            let unlocked: Vec<&str> = manifest
+                .tools
+                .iter()
+                .filter(|t| lockfile.get(t, &triple).is_none())
+                .map(|t| t.as_str())
+                .collect();
+            if !unlocked.is_empty() {
+                anyhow::bail!(
+                    "{} has no {} entry for {}. Run forge apply without --locked to resolve them",
+                    lock_path.display(),
+                    triple,
+                    unlocked.join(", ")
+                );
+            }
+        }
+
+        let facts = Facts::load_from(&self.paths).await?;
+        let mut pending: Vec<(&str, Option<&str>)> = Vec::new();
+
+        for tool_name in &manifest.tools {
+            let preferred = manifest.installer_for(tool_name);
+            let entry = lockfile.get(tool_name, &triple);
+
+            match facts.tools.get(tool_name) {
+                Some(fact) if !locked || entry.is_some_and(|e| e.matches(fact)) => {
+                    outln!(
+                        "  {} {} {} ({})",
+                        SUCCESS,
+                        Colors::success(tool_name),
+                        Colors::muted(&format!(
+                            "v{}",
+                            fact.version.as_deref().unwrap_or("unknown")
+                        )),
+                        fact.installer
+                    );
+                    if let Some(entry) = entry
+                        && !entry.matches(fact)
+                    {
+                        outln!(
+                            "     {} {} pins v{} via {}; run {} to match",
+                            INFO,
+                            LOCK_FILE,
+                            entry.version,
+                            entry.installer,
+                            Colors::action("forge apply --locked")
+                        );
+                    } else if let Some(preferred) = preferred
+                        && preferred != fact.installer
+                    {
+                        outln!(
+                            "     {} project prefers {}; run {} to switch",
+                            INFO,
+                            Colors::action(preferred),
+                            Colors::action(&format!(
+                                "forge install {} -i {}",
+                                tool_name, preferred
+                            ))
+                        );
+                    }
+                }
+                Some(fact) => {
+                    let entry = entry.expect("checked above");
+                    outln!(
+                        "  {} {} v{} ({}), {} pins v{} ({})",
+                        WARNING,
+                        Colors::warning(tool_name),
+                        fact.version.as_deref().unwrap_or("unknown"),
+                        fact.installer,
+                        LOCK_FILE,
+                        entry.version,
+                        entry.installer
+                    );
+                    pending.push((tool_name.as_str(), preferred));
+                }
+                None => {
+                    outln!(
+                        "  {} {} {}",
+                        crate::color::PACKAGE,
+                        Colors::warning(tool_name),
+                        Colors::muted("missing")
+                    );
+                    pending.push((tool_name.as_str(), preferred));
+                }
+            }
+        }
+
+        if check && !pending.is_empty() {
+            anyhow::bail!(
+                "{} of {} project tools {}",
+                pending.len(),
+                manifest.tools.len(),
+                if locked {
+                    "do not match forge.lock"
+                } else {
+                    "are missing"
+                }
+            );
+        }
 
-            // Find unique installers used by installed tools
-            let mut installers_to_update = std::collections::HashSet::new();
-            for (_, fact) in &facts.tools {
-                installers_to_update.insert(&fact.installer);
-            }
+        // What each pending tool installs from, and the installer that
+        // decides whether it can run alongside the others
+        type Source = (Option<String>, Option<LockedTool>);
+        let mut sources: BTreeMap<String, Source> = BTreeMap::new();
+        let mut jobs = Vec::new();
+        for (tool_name, preferred) in &pending {
+            let entry = lockfile.get(tool_name, &triple).filter(|_| locked).cloned();
+            let installer = match &entry {
+                Some(entry) => Some(entry.installer.clone()),
+                None => preferred
+                    .map(str::to_string)
+                    .or_else(|| self.resolve_installer(tool_name)),
+            };
+            jobs.push((tool_name.to_string(), installer.unwrap_or_default()));
+            sources.insert(
+                tool_name.to_string(),
+                (preferred.map(str::to_string), entry),
+            );
+        }
 
-            // Update each installer's provider
-            for installer_name in installers_to_update {
-                if let Some(provider_tool) = self.find_tool_that_provides(installer_name) {
-                    if let Some(installer) = self.knowledge.installers.get(installer_name) {
-                        if installer.update.is_some() {
-                            println!(
-                                "  {} Updating {} (provides {})",
-                                ACTION,
-                                Colors::info(&provider_tool.0),
-                                installer_name
-                            );
-
-                            // Execute the update
-                            if let Err(e) = self
-                                .execute_installer_update(&provider_tool.0, installer_name)
-                                .await
-                            {
-                                println!(
-                                    "  {} Failed to update {}: {}",
-                                    WARNING, provider_tool.0, e
-                                );
-                            }
-                        }
+        // Refuse, before anything runs, to install a locked version the
+        // installer would have to guess at
+        let unsupported: Vec<String> = sources
+            .iter()
+            .filter_map(|(tool_name, (_, entry))| {
+                let entry = entry.as_ref()?;
+                let installer = self.knowledge.installers.get(&entry.installer)?;
+                crate::backend::ensure_version_supported(
+                    &entry.installer,
+                    installer,
+                    tool_name,
+                    Some(&entry.version),
+                )
+                .err()
+            })
+            .map(|e| e.to_string())
+            .collect();
+        if !unsupported.is_empty() {
+            anyhow::bail!(
+                "{} pins versions its installers cannot request:\n  {}",
+                LOCK_FILE,
+                unsupported.join("\n  ")
+            );
+        }
+
+        let sources = Arc::new(sources);
+        let results = self
+            .run_jobs(jobs, move |forge, tool_name| {
+                let sources = sources.clone();
+                async move {
+                    outln!();
+                    match &sources[&tool_name] {
+                        (_, Some(entry)) => forge.install_locked(&tool_name, entry).await,
+                        (preferred, None) => forge.install(&tool_name, preferred.as_deref()).await,
                     }
                 }
+            })
+            .await;
+
+        let mut failed = Vec::new();
+        for (tool_name, result) in results {
+            if let Err(e) = result {
+                outln!("{} {}: {}", ERROR, Colors::error(&tool_name), e);
+                failed.push(tool_name);
             }
         }
 
-        // Perform updates
-        for (tool_name, installer_name, _version) in updates {
-            println!("\n{} Updating {}...", ACTION, Colors::info(&tool_name));
-
-            // Uninstall old version first if uninstall command exists
-            if let Some(installer) = self.knowledge.installers.get(&installer_name) {
-                if installer.uninstall.is_some() {
-                    self.uninstall(&tool_name).await?;
+        // Record what was resolved here, keeping existing pins
+        if !check && !locked && !self.dry_run {
+            let facts = Facts::load_from(&self.paths).await?;
+            let before = lockfile.tools.clone();
+            for tool_name in &manifest.tools {
+                if lockfile.get(tool_name, &triple).is_none()
+                    && let Some(entry) = facts.tools.get(tool_name).and_then(LockedTool::from_fact)
+                {
+                    lockfile.insert(tool_name, &triple, entry);
                 }
             }
+            lockfile.retain_tools(&manifest.tools);
 
-            // Install new version
-            self.install(&tool_name, Some(&installer_name)).await?;
+            if lockfile.tools != before || !lock_path.exists() {
+                lockfile.save(&lock_path)?;
+                outln!(
+                    "\n{} Updated {} for {}",
+                    INFO,
+                    Colors::muted(&lock_path.display().to_string()),
+                    triple
+                );
+            }
         }
 
-        println!("\n{} Updates complete!", SUCCESS);
+        if !failed.is_empty() {
+            anyhow::bail!("Failed to install {}", failed.join(", "));
+        }
+        if pending.is_empty() {
+            outln!(
+                "\n{} All {} project tools are installed",
+                SUCCESS,
+                manifest.tools.len()
+            );
+        } else if self.dry_run {
+            outln!("\n{} Dry run complete, nothing was changed", DRY_RUN);
+        } else {
+            outln!("\n{} Project tools are installed", SUCCESS);
+        }
         Ok(())
     }
 
-    pub async fn uninstall(&self, tool_name: &str) -> Result<()> {
-        println!(
-            "{} Preparing to uninstall {}...",
-            ACTION,
-            Colors::info(tool_name)
-        );
+    /// Replace whatever is installed with the exact artifacts of a lock entry
+    async fn install_locked(&self, tool_name: &str, entry: &LockedTool) -> Result<()> {
+        let previous = Facts::load_from(&self.paths)
+            .await?
+            .tools
+            .get(tool_name)
+            .cloned();
+
+        // A different installer is switched over by install itself; the same
+        // one has to be told to install again
+        let same_installer = previous
+            .as_ref()
+            .is_some_and(|fact| fact.installer == entry.installer);
+
+        if self.dry_run {
+            if same_installer {
+                let version = self
+                    .knowledge
+                    .installers
+                    .get(&entry.installer)
+                    .filter(|i| entry.installer == "github" || i.install_version.is_some())
+                    .map(|_| entry.version.as_str());
+                return self.preview_install(tool_name, &entry.installer, version);
+            }
+            return self
+                .install_with(tool_name, Some(&entry.installer), None, Some(entry))
+                .await;
+        }
 
-        let mut facts = Facts::load().await?;
+        self.install_transaction(
+            tool_name,
+            Some(&entry.installer),
+            None,
+            Some(entry),
+            true,
+            same_installer,
+        )
+        .await
+    }
 
-        if let Some(fact) = facts.tools.get(tool_name) {
-            let tool = self.knowledge.tools.get(tool_name);
-            let provides: &[_] = tool.as_ref().map_or(&[], |t| &t.provides);
+    /// Print a health report of the environment and forge's own state
+    pub async fn doctor(&self) -> Result<()> {
+        use crate::doctor::Severity;
 
-            // Check if this tool provides any installers
-            if !provides.is_empty() {
-                // Find all tools installed by the installers this tool provides
-                let dependent_tools: Vec<String> = facts
-                    .tools
-                    .iter()
-                    .filter(|(name, f)| *name != tool_name && provides.contains(&f.installer))
-                    .map(|(name, _)| name.clone())
-                    .collect();
+        outln!("{} Checking forge environment...", SEARCH);
 
-                if !dependent_tools.is_empty() {
-                    println!(
-                        "\n{} {} provides the {} installer",
-                        WARNING,
-                        tool_name,
-                        provides.join(", ")
-                    );
-                    println!("The following tools were installed using it:");
-                    for dep in &dependent_tools {
-                        println!("  • {}", Colors::info(dep));
-                    }
-                    println!("\nThese tools will be removed from Forge's records.");
-                    println!("(The actual binaries may also be removed by the uninstaller)");
-                }
+        let findings = self.diagnose().await?;
+        let mut category = "";
+        for finding in &findings {
+            if finding.category != category {
+                category = finding.category;
+                outln!("\n{}", Colors::info(category));
             }
 
-            // No confirmation needed - trust the user
-            println!(
-                "\n{} Uninstalling {}...",
-                ACTION,
-                Colors::warning(tool_name)
-            );
-
-            // Remove the actual executables first
-            if let Some(executables) = &fact.executables {
-                for exe in executables {
-                    let exe_path = dirs::home_dir()
-                        .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-                        .join(".local/bin")
-                        .join(exe);
-
-                    if exe_path.exists() {
-                        println!("  {} Removing executable: {}", ACTION, exe);
-                        std::fs::remove_file(&exe_path)?;
-                    }
-                }
+            let marker = match finding.severity {
+                Severity::Ok => SUCCESS,
+                Severity::Warning => WARNING,
+                Severity::Error => ERROR,
+            };
+            outln!("  {} {}", marker, finding.message);
+            if let Some(fix) = &finding.fix {
+                outln!("     {} {}", TIP, Colors::action(fix));
             }
+        }
 
-            // Try to use uninstall command if available
-            if let Some(installer) = self.knowledge.installers.get(&fact.installer) {
-                if let Some(uninstall_cmd) = &installer.uninstall {
-                    let default = Default::default();
-                    let tool_config = self
-                        .knowledge
-                        .tools
-                        .get(tool_name)
-                        .and_then(|t| t.installers.get(&fact.installer))
-                        .unwrap_or(&default);
-
-                    let mut command = uninstall_cmd.clone();
-                    for part in &mut command {
-                        *part = crate::backend::expand_template(
-                            part,
-                            tool_name,
-                            tool_config,
-                            None,
-                            &self.platform,
-                        );
-                    }
+        let problems = findings.iter().filter(|f| f.is_problem()).count();
+        if problems == 0 {
+            outln!("\n{} No problems found", SUCCESS);
+        } else {
+            outln!(
+                "\n{} {} {} found",
+                WARNING,
+                problems,
+                if problems == 1 { "problem" } else { "problems" }
+            );
+        }
+        Ok(())
+    }
 
-                    println!("{} Running: {}", ACTION, Colors::muted(&command.join(" ")));
-                    let output = Command::new(&command[0]).args(&command[1..]).output()?;
+    /// Run every `forge doctor` check, without printing
+    pub async fn diagnose(&self) -> Result<Vec<crate::doctor::Finding>> {
+        use crate::doctor::{Finding, path_contains, path_fix, shadowing_binary};
 
-                    if !output.status.success() {
-                        println!("{} Uninstall command failed", WARNING);
-                    }
-                } else if installer.installer_type == "script" {
-                    // Use platform-specific uninstall script if available
-                    if let Some(tool_def) = self.knowledge.tools.get(tool_name) {
-                        if let Some(tool_installer) = tool_def.installers.get(&fact.installer) {
-                            let platform_scripts = match self.platform.os.as_str() {
-                                "linux" => &tool_installer.linux,
-                                "macos" => &tool_installer.macos,
-                                "windows" => &tool_installer.windows,
-                                _ => &None,
-                            };
-
-                            if let Some(scripts) = platform_scripts {
-                                if let Some(uninstall_script) = &scripts.uninstall {
-                                    println!("{} Running uninstall script...", ACTION);
-                                    let output = Command::new("sh")
-                                        .arg("-c")
-                                        .arg(uninstall_script)
-                                        .output()?;
-
-                                    if !output.status.success() {
-                                        println!("{} Uninstall script failed", WARNING);
-                                    }
-                                } else {
-                                    println!("{} No uninstaller available for {}", INFO, tool_name);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        let mut findings = Vec::new();
+        let search_path = self.paths.search_path();
+        let bin_dir = self.paths.bin_dir();
 
-            // Remove from facts
-            facts.tools.remove(tool_name);
+        // PATH
+        if path_contains(&search_path, bin_dir) {
+            findings.push(Finding::ok(
+                "PATH",
+                format!("{} is on PATH", bin_dir.display()),
+            ));
+        } else {
+            findings.push(Finding::error(
+                "PATH",
+                format!("{} is not on PATH", bin_dir.display()),
+                path_fix(bin_dir, &self.platform.os),
+            ));
+        }
 
-            // Also remove tools that were installed by this tool's installers
-            if !provides.is_empty() {
-                let tools_to_remove: Vec<String> = facts
-                    .tools
-                    .iter()
-                    .filter(|(_, f)| provides.contains(&f.installer))
-                    .map(|(name, _)| name.clone())
-                    .collect();
+        // Installers this platform would pick from
+        let precedence = self
+            .knowledge
+            .platforms
+            .get(&self.platform.os)
+            .map(|p| p.precedence.clone())
+            .unwrap_or_default();
+        for name in &precedence {
+            let Some(installer) = self.knowledge.installers.get(name) else {
+                continue;
+            };
+            let Some(check) = installer.check.as_ref().filter(|c| !c.is_empty()) else {
+                continue;
+            };
 
-                for tool in tools_to_remove {
-                    println!("{} Removing {} from records", ACTION, Colors::muted(&tool));
-                    facts.tools.remove(&tool);
+            match self.runner.run(&check[0], &check[1..]) {
+                Ok(output) if output.status.success() => {
+                    findings.push(Finding::ok("Installers", format!("{} is available", name)));
+                }
+                _ => {
+                    let fix = match self.find_tool_that_provides(name) {
+                        Some((provider, _)) => format!("forge install {}", provider),
+                        None => format!("Install {} with your system package manager", name),
+                    };
+                    findings.push(Finding::warning(
+                        "Installers",
+                        format!("{} is not available (`{}` failed)", name, check.join(" ")),
+                        fix,
+                    ));
                 }
             }
+        }
 
-            facts.save().await?;
-
-            println!("{} {} uninstalled", SUCCESS, Colors::success(tool_name));
+        // GitHub CLI, used by share, load and sync
+        let gh_installed = self
+            .runner
+            .run("gh", &["--version".to_string()])
+            .is_ok_and(|o| o.status.success());
+        if !gh_installed {
+            findings.push(Finding::warning(
+                "GitHub",
+                "gh is not installed; share, load and sync need it",
+                "forge install gh",
+            ));
+        } else if crate::sync::check_gh_auth(self.runner.as_ref()).is_ok() {
+            findings.push(Finding::ok("GitHub", "gh is authenticated"));
         } else {
-            println!("{} {} is not installed", INFO, tool_name);
+            findings.push(Finding::warning(
+                "GitHub",
+                "gh is not authenticated",
+                "gh auth login",
+            ));
         }
 
-        Ok(())
-    }
-
-    pub fn why(&self, tool_name: &str) -> Result<()> {
-        let tool = self
-            .knowledge
-            .tools
-            .get(tool_name)
-            .ok_or_else(|| anyhow::anyhow!("Unknown tool: {}", tool_name))?;
+        // Local knowledge
+        let local = self.paths.local_knowledge();
+        if local.exists() {
+            match Knowledge::check_local(&local) {
+                Ok(()) => findings.push(Finding::ok(
+                    "Knowledge",
+                    format!("{} parses", local.display()),
+                )),
+                Err(e) => findings.push(Finding::error(
+                    "Knowledge",
+                    format!(
+                        "{} is ignored because it does not parse: {}",
+                        local.display(),
+                        e.to_string().trim()
+                    ),
+                    format!("$EDITOR {}", local.display()),
+                )),
+            }
+        }
 
-        println!("{}", Colors::info(tool_name));
-        println!("{}", Colors::muted(&tool.description));
+        // Facts
+        let facts = match Facts::load_from(&self.paths).await {
+            Ok(facts) => facts,
+            Err(e) => {
+                let facts_file = self.paths.facts_file();
+                findings.push(Finding::error(
+                    "Facts",
+                    format!("{} does not parse: {}", facts_file.display(), e),
+                    format!("$EDITOR {}", facts_file.display()),
+                ));
+                return Ok(findings);
+            }
+        };
 
-        Ok(())
-    }
+        let mut names: Vec<&String> = facts.tools.keys().collect();
+        names.sort();
+        let mut facts_ok = true;
+        let mut shadowing = Vec::new();
 
-    pub async fn list(&self) -> Result<()> {
-        let facts = Facts::load().await?;
+        for name in names {
+            let fact = &facts.tools[name];
+            let tool = self.knowledge.tools.get(name);
 
-        if facts.tools.is_empty() {
-            println!("{}", Colors::muted("No tools installed yet."));
-            println!("\nGet started with:");
-            println!("  {}", Colors::action("forge install ripgrep"));
-            return Ok(());
-        }
+            if tool.is_none() {
+                facts_ok = false;
+                findings.push(Finding::warning(
+                    "Facts",
+                    format!("{} is recorded as installed but is not in knowledge", name),
+                    format!("forge uninstall {}", name),
+                ));
+            }
 
-        // Calculate column widths
-        let mut max_name_len = 0;
-        let mut max_version_len = 0;
-        let mut max_installer_len = 0;
+            let executables = self.installed_executables(name, fact, tool);
+            let missing: Vec<&str> = executables
+                .iter()
+                .filter(|(_, path)| path.is_none())
+                .map(|(exe, _)| exe.as_str())
+                .collect();
+            if !missing.is_empty() {
+                facts_ok = false;
+                findings.push(Finding::warning(
+                    "Facts",
+                    format!(
+                        "{} is recorded but {} no longer {}",
+                        name,
+                        missing.join(", "),
+                        if missing.len() == 1 {
+                            "exists"
+                        } else {
+                            "exist"
+                        }
+                    ),
+                    format!("forge uninstall {} && forge install {}", name, name),
+                ));
+            }
 
-        for (name, fact) in &facts.tools {
-            max_name_len = max_name_len.max(name.len());
-            let version_len = fact.version.as_deref().unwrap_or("unknown").len() + 1; // +1 for 'v' prefix
-            max_version_len = max_version_len.max(version_len);
-            max_installer_len = max_installer_len.max(fact.installer.len());
+            for (exe, path) in &executables {
+                if let Some(path) = path
+                    && let Some(winner) = shadowing_binary(exe, path, &search_path)
+                {
+                    shadowing.push(Finding::warning(
+                        "Shadowing",
+                        format!(
+                            "{} from {} ({}) is shadowed by {}",
+                            exe,
+                            name,
+                            path.display(),
+                            winner.display()
+                        ),
+                        match path.parent() {
+                            Some(dir) => path_fix(dir, &self.platform.os),
+                            None => format!("rm {}", winner.display()),
+                        },
+                    ));
+                }
+            }
         }
 
-        println!("Installed tools:");
-        for (name, fact) in &facts.tools {
-            let tool = self.knowledge.tools.get(name);
-            let description = tool
-                .map(|t| t.description.as_str())
-                .unwrap_or("Unknown tool");
-            let version = fact.version.as_deref().unwrap_or("unknown");
+        if facts.tools.is_empty() {
+            findings.push(Finding::ok("Facts", "No tools recorded yet"));
+        } else if facts_ok {
+            findings.push(Finding::ok(
+                "Facts",
+                format!("All {} recorded tools are present", facts.tools.len()),
+            ));
+        }
+        if shadowing.is_empty() {
+            findings.push(Finding::ok(
+                "Shadowing",
+                "No installed executable is shadowed",
+            ));
+        }
+        findings.extend(shadowing);
 
-            // Add (local) marker if from local overlay
-            let local_marker = if self.knowledge.local_tools.contains(name) {
-                " (local)"
-            } else {
-                ""
-            };
+        Ok(findings)
+    }
 
-            println!(
-                "  • {}{} {} - {}",
-                Colors::info(&name),
-                Colors::muted(local_marker),
-                Colors::muted(&format!("v{}", version)),
-                Colors::muted(description)
-            );
+    /// Where each executable of an installed tool lives, `None` when missing
+    fn installed_executables(
+        &self,
+        tool_name: &str,
+        fact: &ToolFact,
+        tool: Option<&Tool>,
+    ) -> Vec<(String, Option<PathBuf>)> {
+        if let Some(provenance) = &fact.provenance
+            && !provenance.executables.is_empty()
+        {
+            return provenance
+                .executables
+                .iter()
+                .map(|(exe, record)| {
+                    (
+                        exe.clone(),
+                        record.path.exists().then(|| record.path.clone()),
+                    )
+                })
+                .collect();
         }
 
-        Ok(())
+        let names = match (&fact.executables, tool) {
+            (Some(names), _) => names.clone(),
+            (None, Some(tool)) if !tool.provides.is_empty() => tool.provides.clone(),
+            _ => vec![tool_name.to_string()],
+        };
+
+        let search_path = self.paths.search_path();
+        let cwd = std::env::current_dir().unwrap_or_default();
+        names
+            .into_iter()
+            .map(|exe| {
+                let in_bin_dir = self.paths.bin_dir().join(&exe);
+                let path = if in_bin_dir.is_file() {
+                    Some(in_bin_dir)
+                } else {
+                    which::which_in(&exe, Some(&search_path), &cwd).ok()
+                };
+                (exe, path)
+            })
+            .collect()
     }
 
     pub async fn fmt(&self, file: Option<&str>, check: bool) -> Result<()> {
         use crate::format::{find_knowledge_files, format_toml};
 
-        println!("{} Formatting TOML files...", INFO);
+        outln!("{} Formatting TOML files...", INFO);
 
-        let files = find_knowledge_files(file).await?;
+        let files = find_knowledge_files(file, &self.paths).await?;
         let mut all_formatted = true;
 
         for file in files {
@@ -615,13 +3099,10 @@ impl Forge {
         };
 
         // Check gh CLI and auth
-        check_gh_auth()?;
+        check_gh_auth(self.runner.as_ref())?;
 
         // Check if local knowledge exists
-        let local_path = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-            .join(".forge")
-            .join("forge.toml");
+        let local_path = self.paths.local_knowledge();
 
         if !local_path.exists() {
             anyhow::bail!(
@@ -635,13 +3116,18 @@ impl Forge {
         let content_hash = hash_file_contents(&content);
 
         // Load facts to check if we already have a gist
-        let mut facts = Facts::load().await?;
+        let mut facts = Facts::load_from(&self.paths).await?;
 
         if let Some(sync_config) = facts.sync.clone() {
             // Changed: use clone() instead of as_ref()
             // Update existing gist
-            println!("{} Updating your gist...", ACTION);
-            update_gist(&sync_config.gist_id, &content, "forge.toml")?;
+            outln!("{} Updating your gist...", ACTION);
+            update_gist(
+                &sync_config.gist_id,
+                &content,
+                "forge.toml",
+                self.runner.as_ref(),
+            )?;
 
             // Update sync metadata
             facts.sync = Some(SyncConfig {
@@ -650,17 +3136,18 @@ impl Forge {
                 last_hash: content_hash,
                 last_sync: Utc::now(),
             });
-            facts.save().await?;
+            facts.save_to(&self.paths).await?;
 
-            println!(
+            outln!(
                 "{} Updated: {}",
                 SUCCESS,
                 Colors::info(&sync_config.gist_url)
             );
         } else {
             // Create new gist
-            println!("{} Creating gist...", ACTION);
-            let (gist_id, gist_url) = create_gist(&content, "forge.toml", private)?;
+            outln!("{} Creating gist...", ACTION);
+            let (gist_id, gist_url) =
+                create_gist(&content, "forge.toml", private, self.runner.as_ref())?;
 
             // Save sync config to facts
             facts.sync = Some(SyncConfig {
@@ -669,10 +3156,10 @@ impl Forge {
                 last_hash: content_hash,
                 last_sync: Utc::now(),
             });
-            facts.save().await?;
+            facts.save_to(&self.paths).await?;
 
-            println!("{} Created: {}", SUCCESS, Colors::info(&gist_url));
-            println!("\n{} Sync with: {}", TIP, Colors::action("forge sync"));
+            outln!("{} Created: {}", SUCCESS, Colors::info(&gist_url));
+            outln!("\n{} Sync with: {}", TIP, Colors::action("forge sync"));
         }
 
         Ok(())
@@ -683,19 +3170,16 @@ impl Forge {
         use crate::sync::{check_gh_auth, download_gist};
 
         // Check gh CLI and auth
-        check_gh_auth()?;
+        check_gh_auth(self.runner.as_ref())?;
 
-        println!("{} Downloading forge.toml...", ACTION);
-        let content = download_gist(url)?;
+        outln!("{} Downloading forge.toml...", ACTION);
+        let content = download_gist(url, self.runner.as_ref())?;
 
         // Validate TOML
         let downloaded: toml::Value =
             toml::from_str(&content).context("Downloaded file is not valid TOML")?;
 
-        let local_path = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-            .join(".forge")
-            .join("forge.toml");
+        let local_path = self.paths.local_knowledge();
 
         // Ensure directory exists
         if let Some(parent) = local_path.parent() {
@@ -707,15 +3191,15 @@ impl Forge {
             if local_path.exists() {
                 // Backup existing
                 let backup_path = local_path.with_extension("toml.bak");
-                println!("{} Backing up to {}", INFO, backup_path.display());
+                outln!("{} Backing up to {}", INFO, backup_path.display());
                 tokio::fs::copy(&local_path, &backup_path).await?;
             }
 
             tokio::fs::write(&local_path, &content).await?;
-            println!("{} Loaded forge.toml", SUCCESS);
+            outln!("{} Loaded forge.toml", SUCCESS);
         } else {
             // Merge mode
-            println!("{} Merging with your local knowledge:", SEARCH);
+            outln!("{} Merging with your local knowledge:", SEARCH);
 
             // Read existing
             let existing_content = tokio::fs::read_to_string(&local_path).await?;
@@ -727,26 +3211,24 @@ impl Forge {
 
             if let (Some(existing_table), Some(downloaded_table)) =
                 (existing.as_table_mut(), downloaded.as_table())
-            {
-                if let Some(downloaded_tools) =
+                && let Some(downloaded_tools) =
                     downloaded_table.get("tools").and_then(|t| t.as_table())
-                {
-                    let existing_tools = existing_table
-                        .entry("tools")
-                        .or_insert(toml::Value::Table(toml::map::Map::new()))
-                        .as_table_mut()
-                        .unwrap();
-
-                    for (name, tool) in downloaded_tools {
-                        if existing_tools.contains_key(name) {
-                            modified += 1;
-                            println!("  ~ {} (updated)", Colors::info(name));
-                        } else {
-                            added += 1;
-                            println!("  + {} (new)", Colors::success(name));
-                        }
-                        existing_tools.insert(name.clone(), tool.clone());
+            {
+                let existing_tools = existing_table
+                    .entry("tools")
+                    .or_insert(toml::Value::Table(toml::map::Map::new()))
+                    .as_table_mut()
+                    .unwrap();
+
+                for (name, tool) in downloaded_tools {
+                    if existing_tools.contains_key(name) {
+                        modified += 1;
+                        outln!("  ~ {} (updated)", Colors::info(name));
+                    } else {
+                        added += 1;
+                        outln!("  + {} (new)", Colors::success(name));
                     }
+                    existing_tools.insert(name.clone(), tool.clone());
                 }
             }
 
@@ -754,9 +3236,11 @@ impl Forge {
             let merged_content = toml::to_string_pretty(&existing)?;
             tokio::fs::write(&local_path, merged_content).await?;
 
-            println!(
+            outln!(
                 "\n{} Merged: {} added, {} modified",
-                SUCCESS, added, modified
+                SUCCESS,
+                added,
+                modified
             );
         }
 
@@ -769,16 +3253,16 @@ impl Forge {
             check_gh_auth, download_gist, get_github_user, hash_file_contents, update_gist,
         };
 
-        let mut facts = Facts::load().await?;
+        let mut facts = Facts::load_from(&self.paths).await?;
 
         if disable {
             // Disable sync
             if facts.sync.is_some() {
                 facts.sync = None;
-                facts.save().await?;
-                println!("{} Sync disabled", SUCCESS);
+                facts.save_to(&self.paths).await?;
+                outln!("{} Sync disabled", SUCCESS);
             } else {
-                println!("{} Sync was not enabled", INFO);
+                outln!("{} Sync was not enabled", INFO);
             }
             return Ok(());
         }
@@ -787,8 +3271,8 @@ impl Forge {
         let sync_config = match &facts.sync {
             Some(config) => config.clone(),
             None => {
-                println!("{} No sync configured", ERROR);
-                println!(
+                outln!("{} No sync configured", ERROR);
+                outln!(
                     "{} First share your knowledge with: {}",
                     crate::color::TIP,
                     Colors::action("forge share")
@@ -798,10 +3282,10 @@ impl Forge {
         };
 
         // Check gh CLI and auth
-        check_gh_auth()?;
+        check_gh_auth(self.runner.as_ref())?;
 
         // Get current user
-        let current_user = get_github_user()?;
+        let current_user = get_github_user(self.runner.as_ref())?;
 
         // Check if we own this gist
         let gist_owner = sync_config
@@ -811,11 +3295,12 @@ impl Forge {
             .unwrap_or("");
 
         if gist_owner != current_user {
-            println!(
+            outln!(
                 "{} You don't own this gist (owner: {})",
-                WARNING, gist_owner
+                WARNING,
+                gist_owner
             );
-            println!(
+            outln!(
                 "{} Create your own with: {}",
                 crate::color::TIP,
                 Colors::action("forge share")
@@ -824,13 +3309,10 @@ impl Forge {
         }
 
         // Read local content
-        let local_path = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-            .join(".forge")
-            .join("forge.toml");
+        let local_path = self.paths.local_knowledge();
 
         if !local_path.exists() {
-            println!("{} No local knowledge to sync", WARNING);
+            outln!("{} No local knowledge to sync", WARNING);
             return Ok(());
         }
 
@@ -841,8 +3323,8 @@ impl Forge {
         let local_changed = local_hash != sync_config.last_hash;
 
         // Download remote
-        println!("{} Checking for remote changes...", ACTION);
-        let remote_content = download_gist(&sync_config.gist_url)?;
+        outln!("{} Checking for remote changes...", ACTION);
+        let remote_content = download_gist(&sync_config.gist_url, self.runner.as_ref())?;
         let remote_hash = hash_file_contents(&remote_content);
 
         // Check if remote has changed since our last sync
@@ -850,12 +3332,12 @@ impl Forge {
 
         match (local_changed, remote_changed) {
             (false, false) => {
-                println!(
+                outln!(
                     "{} Already synced with: {}",
                     SUCCESS,
                     Colors::info(&sync_config.gist_url)
                 );
-                println!(
+                outln!(
                     "{} Last sync: {} ago",
                     INFO,
                     Colors::muted(&format_duration_since(sync_config.last_sync))
@@ -863,8 +3345,13 @@ impl Forge {
             }
             (true, false) => {
                 // Only local changed - push
-                println!("{} Pushing local changes...", ACTION);
-                update_gist(&sync_config.gist_id, &local_content, "forge.toml")?;
+                outln!("{} Pushing local changes...", ACTION);
+                update_gist(
+                    &sync_config.gist_id,
+                    &local_content,
+                    "forge.toml",
+                    self.runner.as_ref(),
+                )?;
 
                 // Update facts
                 facts.sync = Some(SyncConfig {
@@ -873,13 +3360,13 @@ impl Forge {
                     last_hash: local_hash,
                     last_sync: Utc::now(),
                 });
-                facts.save().await?;
+                facts.save_to(&self.paths).await?;
 
-                println!("{} Pushed local changes", SUCCESS);
+                outln!("{} Pushed local changes", SUCCESS);
             }
             (false, true) => {
                 // Only remote changed - pull
-                println!("{} Pulling remote changes...", ACTION);
+                outln!("{} Pulling remote changes...", ACTION);
 
                 // Backup local
                 let backup_path = local_path.with_extension("toml.bak");
@@ -895,20 +3382,20 @@ impl Forge {
                     last_hash: remote_hash,
                     last_sync: Utc::now(),
                 });
-                facts.save().await?;
+                facts.save_to(&self.paths).await?;
 
-                println!("{} Pulled remote changes", SUCCESS);
+                outln!("{} Pulled remote changes", SUCCESS);
             }
             (true, true) => {
                 // Both changed - conflict
-                println!("{} Remote has changes:", WARNING);
+                outln!("{} Remote has changes:", WARNING);
 
                 // Show what's different (simple version)
                 // In a real implementation, we'd parse and compare the TOML
-                println!("\nHow to proceed?");
-                println!("  1) Pull remote changes, then push yours");
-                println!("  2) Force push your version");
-                println!("  3) Cancel");
+                outln!("\nHow to proceed?");
+                outln!("  1) Pull remote changes, then push yours");
+                outln!("  2) Force push your version");
+                outln!("  3) Cancel");
                 print!("Choice [1]: ");
 
                 use std::io::{self, Write};
@@ -921,13 +3408,13 @@ impl Forge {
                 match choice {
                     "" | "1" => {
                         // Pull then push
-                        println!("{} Pulling remote changes...", ACTION);
+                        outln!("{} Pulling remote changes...", ACTION);
                         tokio::fs::write(&local_path, &remote_content).await?;
 
                         // Now merge local changes back...
                         // For now, just tell user to re-edit
-                        println!("{} Remote changes pulled", SUCCESS);
-                        println!(
+                        outln!("{} Remote changes pulled", SUCCESS);
+                        outln!(
                             "{} Re-apply your local changes and run {} again",
                             INFO,
                             Colors::action("forge sync")
@@ -935,8 +3422,13 @@ impl Forge {
                     }
                     "2" => {
                         // Force push
-                        println!("{} Force pushing your version...", ACTION);
-                        update_gist(&sync_config.gist_id, &local_content, "forge.toml")?;
+                        outln!("{} Force pushing your version...", ACTION);
+                        update_gist(
+                            &sync_config.gist_id,
+                            &local_content,
+                            "forge.toml",
+                            self.runner.as_ref(),
+                        )?;
 
                         facts.sync = Some(SyncConfig {
                             gist_id: sync_config.gist_id,
@@ -944,12 +3436,12 @@ impl Forge {
                             last_hash: local_hash,
                             last_sync: Utc::now(),
                         });
-                        facts.save().await?;
+                        facts.save_to(&self.paths).await?;
 
-                        println!("{} Force pushed your version", SUCCESS);
+                        outln!("{} Force pushed your version", SUCCESS);
                     }
                     _ => {
-                        println!("{} Cancelled", INFO);
+                        outln!("{} Cancelled", INFO);
                     }
                 }
             }
@@ -961,53 +3453,57 @@ impl Forge {
     async fn execute_installer_update(&self, tool_name: &str, installer_name: &str) -> Result<()> {
         // For script installers, use platform-specific update script
         if installer_name == "script" {
-            if let Some(tool) = self.knowledge.tools.get(tool_name) {
-                if let Some(tool_installer) = tool.installers.get("script") {
-                    let platform_scripts = match self.platform.os.as_str() {
-                        "linux" => &tool_installer.linux,
-                        "macos" => &tool_installer.macos,
-                        "windows" => &tool_installer.windows,
-                        _ => return Ok(()),
-                    };
-
-                    if let Some(scripts) = platform_scripts {
-                        if let Some(update_script) = &scripts.update {
-                            let output =
-                                Command::new("sh").arg("-c").arg(update_script).output()?;
+            if let Some(tool) = self.knowledge.tools.get(tool_name)
+                && let Some(tool_installer) = tool.installers.get("script")
+            {
+                let platform_scripts = match self.platform.os.as_str() {
+                    "linux" => &tool_installer.linux,
+                    "macos" => &tool_installer.macos,
+                    "windows" => &tool_installer.windows,
+                    _ => return Ok(()),
+                };
 
-                            if !output.status.success() {
-                                let stderr = String::from_utf8_lossy(&output.stderr);
-                                anyhow::bail!("Update script failed: {}", stderr);
-                            }
+                if let Some(scripts) = platform_scripts
+                    && let Some(update_script) = &scripts.update
+                {
+                    if self.dry_run {
+                        outln!("  {} Would run update script:", DRY_RUN);
+                        for line in update_script.trim().lines() {
+                            outln!("    {}", Colors::muted(line));
                         }
+                        return Ok(());
+                    }
+
+                    let output = self
+                        .runner
+                        .run("sh", &["-c".to_string(), update_script.clone()])?;
+
+                    if !output.status.success() {
+                        let stderr = String::from_utf8_lossy(&output.stderr);
+                        anyhow::bail!("Update script failed: {}", stderr);
                     }
                 }
             }
         } else {
             // For command installers, use the update command if available
-            if let Some(installer) = self.knowledge.installers.get(installer_name) {
-                if let Some(update_cmd) = &installer.update {
-                    if let Some(tool) = self.knowledge.tools.get(tool_name) {
-                        if let Some(tool_installer) = tool.installers.get(installer_name) {
-                            let mut command = update_cmd.clone();
-                            for part in &mut command {
-                                *part = crate::backend::expand_template(
-                                    part,
-                                    tool_name,
-                                    tool_installer,
-                                    None,
-                                    &self.platform,
-                                );
-                            }
+            if let Some(installer) = self.knowledge.installers.get(installer_name)
+                && let Some(update_cmd) = &installer.update
+                && let Some(tool) = self.knowledge.tools.get(tool_name)
+                && let Some(tool_installer) = tool.installers.get(installer_name)
+            {
+                let command =
+                    expand_command(update_cmd, tool_name, tool_installer, None, &self.platform);
 
-                            let output = Command::new(&command[0]).args(&command[1..]).output()?;
+                if self.dry_run {
+                    outln!("  {} Would run: {}", DRY_RUN, command.join(" "));
+                    return Ok(());
+                }
 
-                            if !output.status.success() {
-                                let stderr = String::from_utf8_lossy(&output.stderr);
-                                anyhow::bail!("Update command failed: {}", stderr);
-                            }
-                        }
-                    }
+                let output = self.runner.run(&command[0], &command[1..])?;
+
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    anyhow::bail!("Update command failed: {}", stderr);
                 }
             }
         }
@@ -1018,8 +3514,8 @@ impl Forge {
     fn find_best_installer<'a>(
         &self,
         tool_name: &str,
-        tool: &'a crate::knowledge::Tool,
-    ) -> Result<(String, &'a crate::knowledge::ToolInstaller)> {
+        tool: &'a Tool,
+    ) -> Result<InstallerSelection<'a>> {
         // Get platform precedence
         let platform_name = &self.platform.os;
         let precedence = self
@@ -1029,14 +3525,37 @@ impl Forge {
             .map(|p| &p.precedence)
             .ok_or_else(|| anyhow::anyhow!("No platform config for {}", platform_name))?;
 
-        // Find first available installer in precedence order
+        // Find first usable installer in precedence order, remembering why
+        // earlier candidates were passed over
+        let mut skipped = Vec::new();
         for installer_name in precedence {
-            if let Some(tool_installer) = tool.installers.get(installer_name) {
-                // Also verify the installer itself exists in knowledge
-                if self.knowledge.installers.contains_key(installer_name) {
-                    return Ok((installer_name.clone(), tool_installer));
+            let Some(tool_installer) = tool.installers.get(installer_name) else {
+                continue;
+            };
+
+            match self.probe_installer(installer_name, tool_installer) {
+                Ok(()) => {
+                    return Ok(InstallerSelection {
+                        installer: installer_name.clone(),
+                        tool_installer,
+                        skipped,
+                    });
                 }
+                Err(reason) => skipped.push((installer_name.clone(), reason)),
+            }
+        }
+
+        // Every declared candidate failed its check - point at the first one
+        if let Some((first, reason)) = skipped.first() {
+            for (name, reason) in &skipped {
+                outln!(
+                    "{} {} installer: {}",
+                    WARNING,
+                    Colors::warning(name),
+                    Colors::muted(reason)
+                );
             }
+            return Err(self.missing_installer(first, reason));
         }
 
         // If no installer found in precedence, list what's available
@@ -1049,12 +3568,195 @@ impl Forge {
         )
     }
 
+    /// Installers a tool declares that this platform uses, in precedence
+    /// order. A script installer also needs a script for this OS.
+    fn platform_installers(&self, tool: &Tool) -> Vec<String> {
+        let Some(platform) = self.knowledge.platforms.get(&self.platform.os) else {
+            return Vec::new();
+        };
+        platform
+            .precedence
+            .iter()
+            .filter(|name| {
+                tool.installers.get(*name).is_some_and(|ti| {
+                    self.knowledge
+                        .installers
+                        .get(*name)
+                        .is_some_and(|i| i.installer_type != "script")
+                        || get_platform_scripts(ti, &self.platform).is_some()
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Check whether an installer can be used for a tool on this platform.
+    /// Returns the reason when it can't.
+    fn probe_installer(
+        &self,
+        installer_name: &str,
+        tool_installer: &ToolInstaller,
+    ) -> std::result::Result<(), String> {
+        let installer = self
+            .knowledge
+            .installers
+            .get(installer_name)
+            .ok_or_else(|| "not defined in knowledge".to_string())?;
+
+        // Script installers only need a script for this OS
+        if installer.installer_type == "script" {
+            return match get_platform_scripts(tool_installer, &self.platform) {
+                Some(_) => Ok(()),
+                None => Err(format!("no script for {}", self.platform.os)),
+            };
+        }
+
+        let Some(check) = installer.check.as_ref().filter(|c| !c.is_empty()) else {
+            return Ok(());
+        };
+
+        match self.runner.run(&check[0], &check[1..]) {
+            Ok(output) if output.status.success() => Ok(()),
+            Ok(_) => Err(format!("`{}` failed", check.join(" "))),
+            Err(_) => Err(format!("{} not found", check[0])),
+        }
+    }
+
+    /// Explain a missing installer, pointing at the tool that provides it
+    fn missing_installer(&self, installer_key: &str, reason: &str) -> anyhow::Error {
+        // Look for a tool that provides this installer
+        if let Some(provider) = self.find_tool_that_provides(installer_key) {
+            outln!(
+                "\n{} {} installer not available ({})",
+                crate::color::ERROR,
+                installer_key,
+                reason
+            );
+            outln!(
+                "\n{} {} is provided by: {}",
+                crate::color::TIP,
+                installer_key,
+                Colors::info(&provider.0)
+            );
+            outln!("   {}", Colors::muted(&provider.1.description));
+            outln!("\nInstall it with:");
+            outln!(
+                "   {}",
+                Colors::action(&format!("forge install {}", provider.0))
+            );
+
+            anyhow::anyhow!("Missing installer")
+        } else {
+            anyhow::anyhow!(
+                "{} installer not available ({}). Please install it first.",
+                installer_key,
+                reason
+            )
+        }
+    }
+
+    /// Locate and hash installed executables, looking in forge's bin dir
+    /// first, where github and script installs put them, and then on PATH
+    fn record_executables(&self, names: &[String]) -> BTreeMap<String, ExecutableRecord> {
+        let search_path = self.paths.search_path();
+        let cwd = std::env::current_dir().unwrap_or_default();
+        names
+            .iter()
+            .filter_map(|name| {
+                let in_bin_dir = self.paths.bin_dir().join(name);
+                let path = if in_bin_dir.is_file() {
+                    in_bin_dir
+                } else {
+                    which::which_in(name, Some(&search_path), &cwd).ok()?
+                };
+                let sha256 = crate::checksum::sha256_file(&path).ok()?;
+                Some((name.clone(), ExecutableRecord { path, sha256 }))
+            })
+            .collect()
+    }
+
     fn find_tool_that_provides(&self, command: &str) -> Option<(String, &Tool)> {
-        self.knowledge
+        self.tools_that_provide(command).into_iter().next()
+    }
+
+    /// Every tool whose `provides` lists `command`: the one named after it
+    /// first, then the rest by name
+    fn tools_that_provide(&self, command: &str) -> Vec<(String, &Tool)> {
+        let mut tools: Vec<(String, &Tool)> = self
+            .knowledge
             .tools
             .iter()
-            .find(|(_, tool)| tool.provides.contains(&command.to_string()))
+            .filter(|(_, tool)| tool.provides.iter().any(|p| p == command))
             .map(|(name, tool)| (name.clone(), tool))
+            .collect();
+        tools.sort_by(|a, b| (a.0 != command, &a.0).cmp(&(b.0 != command, &b.0)));
+        tools
+    }
+}
+
+/// Whether two paths name the same file, following symlinks
+fn same_file(a: &PathBuf, b: &PathBuf) -> bool {
+    a == b
+        || matches!(
+            (a.canonicalize(), b.canonicalize()),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+/// Split `tool@version` into its parts
+fn split_version(spec: &str) -> (&str, Option<&str>) {
+    match spec.split_once('@') {
+        Some((tool, version)) if !version.is_empty() => (tool, Some(version)),
+        _ => (spec.trim_end_matches('@'), None),
+    }
+}
+
+/// How install output names the release it downloads from
+fn release_label(repo: &str, version: Option<&str>) -> String {
+    match version {
+        Some(version) => format!("the {} release of {}", version, repo),
+        None => format!("the latest {} release", repo),
+    }
+}
+
+/// Print where a tool's binaries came from, as recorded at install time
+fn print_provenance(fact: &ToolFact) {
+    outln!(
+        "      {} {} ({})",
+        Colors::muted("installer:"),
+        fact.installer,
+        fact.installed_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    let Some(provenance) = &fact.provenance else {
+        outln!("      {}", Colors::muted("no provenance recorded"));
+        return;
+    };
+
+    if let Some(tag) = &provenance.tag {
+        outln!("      {} {}", Colors::muted("tag:"), tag);
+    }
+    for download in &provenance.downloads {
+        outln!("      {} {}", Colors::muted("asset:"), download.asset);
+        outln!(
+            "      {} {}",
+            Colors::muted("url:"),
+            Colors::muted(&download.url)
+        );
+    }
+    if let Some(command) = &provenance.command {
+        outln!("      {} {}", Colors::muted("command:"), command.join(" "));
+    }
+    if let Some(hash) = &provenance.script_sha256 {
+        outln!("      {} sha256:{}", Colors::muted("script:"), hash);
+    }
+    for (name, record) in &provenance.executables {
+        outln!(
+            "      {} {} sha256:{}",
+            Colors::muted(&format!("{}:", name)),
+            record.path.display(),
+            Colors::muted(&record.sha256)
+        );
     }
 }
 
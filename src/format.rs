@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use toml::Value;
 
 use crate::color::{Colors, ERROR, SUCCESS};
+use crate::paths::Paths;
 
 /// Format a TOML file according to Forge conventions
 pub async fn format_toml(path: &Path, check_only: bool) -> Result<bool> {
@@ -41,7 +42,10 @@ pub async fn format_toml(path: &Path, check_only: bool) -> Result<bool> {
 }
 
 /// Find forge.toml files to format.
-pub async fn find_knowledge_files(explicit_path: Option<&str>) -> Result<Vec<PathBuf>> {
+pub async fn find_knowledge_files(
+    explicit_path: Option<&str>,
+    paths: &Paths,
+) -> Result<Vec<PathBuf>> {
     if let Some(path) = explicit_path {
         return Ok(vec![PathBuf::from(path)]);
     }
@@ -55,11 +59,9 @@ pub async fn find_knowledge_files(explicit_path: Option<&str>) -> Result<Vec<Pat
     }
 
     // Check ~/.forge/forge.toml
-    if let Some(home) = dirs::home_dir() {
-        let global = home.join(".forge").join("forge.toml");
-        if global.exists() {
-            files.push(global);
-        }
+    let global = paths.local_knowledge();
+    if global.exists() {
+        files.push(global);
     }
 
     if files.is_empty() {
@@ -147,17 +149,17 @@ fn serialize_tool(name: &str, value: &Value) -> Result<String> {
         }
 
         // Then handle installers
-        if let Some(installers) = table.get("installers") {
-            if let Value::Table(installers_table) = installers {
-                let sorted: BTreeMap<_, _> = installers_table.iter().collect();
-                for (installer_name, installer_config) in sorted {
-                    output.push('\n');
-                    output.push_str(&serialize_tool_installer(
-                        name,
-                        installer_name,
-                        installer_config,
-                    )?);
-                }
+        if let Some(installers) = table.get("installers")
+            && let Value::Table(installers_table) = installers
+        {
+            let sorted: BTreeMap<_, _> = installers_table.iter().collect();
+            for (installer_name, installer_config) in sorted {
+                output.push('\n');
+                output.push_str(&serialize_tool_installer(
+                    name,
+                    installer_name,
+                    installer_config,
+                )?);
             }
         }
     }
@@ -231,7 +233,7 @@ fn serialize_tool_installer(
             ));
             output.push_str(&serialize_table_contents(
                 table,
-                &["package", "repo", "pattern", "url"],
+                &["package", "repo", "pattern", "url", "sha256"],
             )?);
         }
     }
@@ -245,8 +247,10 @@ fn serialize_installer_table(table: &toml::map::Map<String, Value>) -> Result<St
     // Define order for installer properties
     let priority_keys = [
         "type",
+        "exclusive",
         "check",
         "install",
+        "install_version",
         "uninstall",
         "install_output_pattern",
         "version_check",
@@ -255,8 +259,8 @@ fn serialize_installer_table(table: &toml::map::Map<String, Value>) -> Result<St
     // Write priority keys first in order
     for key in &priority_keys {
         if let Some(val) = table.get(*key) {
-            match key {
-                &"install_output_pattern" => {
+            match *key {
+                "install_output_pattern" => {
                     // Use raw strings for patterns
                     if let Value::String(s) = val {
                         output.push_str(&format!("{} = '''{}'''\n", key, s));
@@ -264,7 +268,7 @@ fn serialize_installer_table(table: &toml::map::Map<String, Value>) -> Result<St
                         output.push_str(&format!("{} = {}\n", key, serialize_value(val)?));
                     }
                 }
-                &"version_check" => {
+                "version_check" => {
                     // Keep as inline table
                     if let Value::Table(vc_table) = val {
                         output.push_str(&format!(
@@ -1,12 +1,146 @@
-use anyhow::Result;
+use crate::archive::{self, ArchiveKind};
+use crate::checksum;
+use crate::facts::Download;
+use crate::output::outln;
+use anyhow::{Context, Result};
 use serde::Deserialize;
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-#[derive(Debug, Deserialize)]
-struct Asset {
-    name: String,
-    url: String,
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Minimal client for the GitHub Releases REST API
+#[derive(Clone)]
+pub struct GitHubClient {
+    api_url: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl GitHubClient {
+    pub fn new(api_url: impl Into<String>, token: Option<String>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(300)))
+            .user_agent(concat!("forge/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .into();
+
+        Self {
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            token,
+            agent,
+        }
+    }
+
+    /// Client for api.github.com (or `FORGE_GITHUB_API_URL`), authenticated
+    /// with `GITHUB_TOKEN` when it is set
+    pub fn from_env() -> Self {
+        let api_url =
+            std::env::var("FORGE_GITHUB_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+        let token = std::env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|t| !t.trim().is_empty());
+        Self::new(api_url, token)
+    }
+
+    pub fn latest_release(&self, repo: &str) -> Result<Release> {
+        let url = format!("{}/repos/{}/releases/latest", self.api_url, repo);
+        let body = self
+            .get(&url)
+            .with_context(|| format!("Failed to fetch release info for {}", repo))?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// The latest release, or the one tagged `version` (with or without a `v` prefix)
+    pub fn release(&self, repo: &str, version: Option<&str>) -> Result<Release> {
+        let Some(version) = version else {
+            return self.latest_release(repo);
+        };
+
+        let bare = version.trim_start_matches('v');
+        for tag in [version.to_string(), format!("v{}", bare), bare.to_string()] {
+            let url = format!("{}/repos/{}/releases/tags/{}", self.api_url, repo, tag);
+            let body = self
+                .get_optional(&url)
+                .with_context(|| format!("Failed to fetch release {} for {}", tag, repo))?;
+            if let Some(body) = body {
+                return Ok(serde_json::from_str(&body)?);
+            }
+        }
+
+        anyhow::bail!("No release of {} is tagged {}", repo, version)
+    }
+
+    /// Download `url` to `dest`, overwriting it
+    pub fn download(&self, url: &str, dest: &Path) -> Result<()> {
+        let mut response = self.request(url).call()?;
+        let status = response.status().as_u16();
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Failed to download {} (HTTP {})", url, status);
+        }
+
+        let mut file = std::fs::File::create(dest)?;
+        std::io::copy(&mut response.body_mut().as_reader(), &mut file)?;
+        Ok(())
+    }
+
+    /// Fetch a small text asset, such as a checksum listing
+    pub fn download_text(&self, url: &str) -> Result<String> {
+        let mut response = self.request(url).call()?;
+        let status = response.status().as_u16();
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Failed to download {} (HTTP {})", url, status);
+        }
+        Ok(response.body_mut().read_to_string()?)
+    }
+
+    fn get(&self, url: &str) -> Result<String> {
+        self.get_optional(url)?
+            .ok_or_else(|| anyhow::anyhow!("Not found: {}", url))
+    }
+
+    /// Like `get`, but a 404 is `None` rather than an error
+    fn get_optional(&self, url: &str) -> Result<Option<String>> {
+        let mut response = self
+            .request(url)
+            .header("Accept", "application/vnd.github+json")
+            .call()?;
+
+        match response.status().as_u16() {
+            200..=299 => Ok(Some(response.body_mut().read_to_string()?)),
+            404 => Ok(None),
+            401 => anyhow::bail!("GitHub rejected the credentials in GITHUB_TOKEN"),
+            403 | 429 if self.token.is_none() => {
+                anyhow::bail!("GitHub API rate limit reached. Set GITHUB_TOKEN to raise it")
+            }
+            status => anyhow::bail!("GitHub API returned HTTP {} for {}", status, url),
+        }
+    }
+
+    fn request(&self, url: &str) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
+        let request = self.agent.get(url);
+        // Only hand the token to the API itself, not to download hosts
+        match &self.token {
+            Some(token) if url.starts_with(&self.api_url) => {
+                request.header("Authorization", &format!("Bearer {}", token))
+            }
+            _ => request,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -18,72 +152,76 @@ struct ScoredAsset {
 #[derive(Debug, Clone)]
 struct ExecutableInfo {
     name: String,
-    path: String,
+    path: PathBuf,
 }
 
 pub struct DiscoveryResult {
     pub download_url: String,
     pub version: String,
+    pub tag: String,
     pub asset_name: String,
+    /// Published checksum file covering the asset, if the release has one
+    pub checksum_url: Option<String>,
 }
 
+#[derive(Debug)]
 pub struct InstallResult {
     pub version: String,
     pub executables: Vec<String>,
+    /// Digest of the downloaded asset
+    pub sha256: String,
 }
 
-pub fn discover_asset(repo: &str, os: &str, arch: &str) -> Result<DiscoveryResult> {
-    println!("🔍 Discovering assets for {} ({}-{})", repo, os, arch);
-
-    // Get latest release from GitHub using gh release view
-    let output = Command::new("gh")
-        .args(&[
-            "release",
-            "view",
-            "--repo",
-            repo,
-            "--json",
-            "assets,tagName",
-        ])
-        .output()?;
-
-    if !output.status.success() {
-        anyhow::bail!("Failed to fetch release info for {}", repo);
-    }
+/// Assets fetched from one release by [`download_matching`]
+#[derive(Debug)]
+pub struct ReleaseDownload {
+    pub version: String,
+    pub tag: String,
+    pub executables: Vec<String>,
+    pub downloads: Vec<Download>,
+}
 
-    // Parse JSON response
-    #[derive(Debug, Deserialize)]
-    struct ReleaseView {
-        #[serde(rename = "tagName")]
-        tag_name: String,
-        assets: Vec<Asset>,
-    }
+pub fn discover_asset(
+    repo: &str,
+    os: &str,
+    arch: &str,
+    version: Option<&str>,
+    github: &GitHubClient,
+) -> Result<DiscoveryResult> {
+    outln!("🔍 Discovering assets for {} ({}-{})", repo, os, arch);
 
-    let release: ReleaseView = serde_json::from_slice(&output.stdout)?;
+    let release = github.release(repo, version)?;
 
     if release.assets.is_empty() {
-        anyhow::bail!("No assets found in latest release for {}", repo);
+        anyhow::bail!(
+            "No assets found in release {} of {}",
+            release.tag_name,
+            repo
+        );
     }
 
     // Score each asset
     let mut scored_assets: Vec<ScoredAsset> = release
         .assets
-        .into_iter()
+        .iter()
+        .cloned()
         .filter_map(|asset| score_asset(&asset, os, arch).map(|score| ScoredAsset { asset, score }))
         .collect();
 
     // Sort by score (highest first)
-    scored_assets.sort_by(|a, b| b.score.cmp(&a.score));
-
-    if let Some(best) = scored_assets.first() {
-        if best.score > 0 {
-            println!("  Found: {} (score: {})", best.asset.name, best.score);
-            return Ok(DiscoveryResult {
-                download_url: best.asset.url.clone(), // Changed from browser_download_url
-                version: release.tag_name.trim_start_matches('v').to_string(),
-                asset_name: best.asset.name.clone(),
-            });
-        }
+    scored_assets.sort_by_key(|sa| std::cmp::Reverse(sa.score));
+
+    if let Some(best) = scored_assets.first()
+        && best.score > 0
+    {
+        outln!("  Found: {} (score: {})", best.asset.name, best.score);
+        return Ok(DiscoveryResult {
+            download_url: best.asset.browser_download_url.clone(),
+            version: release.tag_name.trim_start_matches('v').to_string(),
+            tag: release.tag_name.clone(),
+            asset_name: best.asset.name.clone(),
+            checksum_url: checksum_url(&release.assets, &best.asset.name),
+        });
     }
 
     // No good match found - provide helpful error
@@ -104,6 +242,55 @@ pub fn discover_asset(repo: &str, os: &str, arch: &str) -> Result<DiscoveryResul
     )
 }
 
+/// Download URL of the checksum asset covering `asset_name`
+fn checksum_url(assets: &[Asset], asset_name: &str) -> Option<String> {
+    let names: Vec<&str> = assets.iter().map(|a| a.name.as_str()).collect();
+    let checksum_name = checksum::find_checksum_asset(&names, asset_name)?;
+    assets
+        .iter()
+        .find(|a| a.name == checksum_name)
+        .map(|a| a.browser_download_url.clone())
+}
+
+/// Collect the digests a download must match: the one pinned in the tool
+/// entry and the one published with the release
+fn expected_digests(
+    asset_name: &str,
+    checksum_url: Option<&str>,
+    pinned_sha256: Option<&str>,
+    github: &GitHubClient,
+) -> Result<Vec<(String, String)>> {
+    let mut expected = Vec::new();
+
+    if let Some(pinned) = pinned_sha256 {
+        expected.push(("pinned digest".to_string(), pinned.to_string()));
+    }
+
+    if let Some(url) = checksum_url {
+        let contents = github.download_text(url)?;
+        let source = url.rsplit('/').next().unwrap_or(url).to_string();
+        match checksum::parse_checksum(&contents, asset_name) {
+            Some(digest) => expected.push((source, digest)),
+            None => outln!("  {} has no entry for {}", source, asset_name),
+        }
+    }
+
+    if expected.is_empty() {
+        outln!("  No checksum published for {}, not verified", asset_name);
+    }
+
+    Ok(expected)
+}
+
+/// Check a download against every expected digest, returning its sha256
+fn verify_download(path: &Path, asset_name: &str, expected: &[(String, String)]) -> Result<String> {
+    let actual = checksum::sha256_file(path)?;
+    for (source, digest) in expected {
+        checksum::ensure_digest(asset_name, &actual, digest, source)?;
+    }
+    Ok(actual)
+}
+
 fn score_asset(asset: &Asset, os: &str, arch: &str) -> Option<i32> {
     let name = asset.name.to_lowercase();
     let mut score = 0;
@@ -114,7 +301,7 @@ fn score_asset(asset: &Asset, os: &str, arch: &str) -> Option<i32> {
         || name.ends_with(".sha256")
         || name.ends_with(".sha512")
         || name.ends_with(".md5")
-        || name.contains(".sha256sum")
+        || checksum::is_checksum_asset(&name)
     {
         return None;
     }
@@ -199,195 +386,194 @@ fn score_asset(asset: &Asset, os: &str, arch: &str) -> Option<i32> {
 }
 
 pub fn download_and_install(
-    url: &str,
-    asset_name: &str,
+    discovery: &DiscoveryResult,
     tool_name: &str,
     provides_hint: &[String],
+    pinned_sha256: Option<&str>,
+    install_dir: &Path,
+    github: &GitHubClient,
 ) -> Result<InstallResult> {
-    // Ensure ~/.local/bin exists
-    std::fs::create_dir_all(
-        dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-            .join(".local/bin"),
+    let asset_name = discovery.asset_name.as_str();
+    let expected = expected_digests(
+        asset_name,
+        discovery.checksum_url.as_deref(),
+        pinned_sha256,
+        github,
     )?;
 
-    let install_dir = dirs::home_dir()
-        .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-        .join(".local/bin");
+    // Ensure ~/.local/bin exists
+    std::fs::create_dir_all(install_dir)?;
 
     // Determine if it's an archive or raw binary
-    let is_archive = asset_name.ends_with(".tar.gz")
-        || asset_name.ends_with(".tgz")
-        || asset_name.ends_with(".zip")
-        || asset_name.ends_with(".tar.xz")
-        || asset_name.ends_with(".tar.bz2");
-
-    if is_archive {
-        // Download to temp file
-        let temp_path = format!("/tmp/{}", asset_name);
-        println!("  Downloading archive to {}", temp_path);
-
-        let status = Command::new("curl")
-            .args(&["-L", "-o", &temp_path, url])
-            .status()?;
-
-        if !status.success() {
-            anyhow::bail!("Failed to download {}", url);
-        }
+    if let Some(kind) = ArchiveKind::from_name(asset_name) {
+        // Download and unpack inside a private temp dir, removed on drop
+        let work_dir = tempfile::Builder::new().prefix("forge-").tempdir()?;
+        let archive_path = work_dir.path().join(asset_name);
+        outln!("  Downloading {}", asset_name);
+
+        github.download(&discovery.download_url, &archive_path)?;
+        let sha256 = verify_download(&archive_path, asset_name, &expected)?;
 
         // Extract and get list of installed executables
-        let executables = extract_and_install(
-            &temp_path,
-            &asset_name,
-            tool_name,
-            &install_dir,
-            provides_hint,
-        )?;
+        outln!("  Extracting archive...");
+        let extract_dir = work_dir.path().join("extracted");
+        archive::extract(&archive_path, kind, &extract_dir)?;
 
-        // Clean up
-        std::fs::remove_file(&temp_path).ok();
+        let executables = install_executables(&extract_dir, tool_name, install_dir, provides_hint)?;
 
         Ok(InstallResult {
             version: String::new(), // Will be filled by caller
             executables,
+            sha256,
         })
     } else {
-        // Raw binary - download directly to install location
+        // Raw binary - download next to the install location, then move it
+        // into place once verified
         let install_path = install_dir.join(tool_name);
-        println!("  Downloading binary to {}", install_path.display());
-
-        let status = Command::new("curl")
-            .args(&["-L", "-o", install_path.to_str().unwrap(), url])
-            .status()?;
-
-        if !status.success() {
-            anyhow::bail!("Failed to download {}", url);
-        }
+        outln!("  Downloading binary to {}", install_path.display());
 
-        // Make executable
-        Command::new("chmod")
-            .args(&["+x", install_path.to_str().unwrap()])
-            .status()?;
+        let download = tempfile::NamedTempFile::new_in(install_dir)?;
+        github.download(&discovery.download_url, download.path())?;
+        let sha256 = verify_download(download.path(), asset_name, &expected)?;
+        download.persist(&install_path)?;
+        make_executable(&install_path)?;
 
         Ok(InstallResult {
             version: String::new(),
             executables: vec![tool_name.to_string()],
+            sha256,
         })
     }
 }
 
-fn extract_and_install(
-    archive_path: &str,
-    archive_name: &str,
-    tool_name: &str,
+/// Download every asset of the latest (or `version`) release whose name
+/// matches a glob pattern (`*` wildcards) straight into `install_dir`
+pub fn download_matching(
+    repo: &str,
+    pattern: &str,
+    version: Option<&str>,
+    pinned_sha256: Option<&str>,
     install_dir: &Path,
-    provides_hint: &[String],
-) -> Result<Vec<String>> {
-    println!("  Extracting archive...");
-
-    if archive_name.ends_with(".tar.gz") || archive_name.ends_with(".tgz") {
-        extract_tar(archive_path, tool_name, install_dir, "z", provides_hint)
-    } else if archive_name.ends_with(".tar.xz") {
-        extract_tar(archive_path, tool_name, install_dir, "J", provides_hint)
-    } else if archive_name.ends_with(".tar.bz2") {
-        extract_tar(archive_path, tool_name, install_dir, "j", provides_hint)
-    } else if archive_name.ends_with(".zip") {
-        extract_zip(archive_path, tool_name, install_dir, provides_hint)
-    } else {
-        anyhow::bail!("Unsupported archive format: {}", archive_name)
-    }
-}
+    github: &GitHubClient,
+) -> Result<ReleaseDownload> {
+    let release = github.release(repo, version)?;
+    let matcher = glob_to_regex(pattern)?;
 
-fn extract_tar(
-    archive_path: &str,
-    tool_name: &str,
-    install_dir: &Path,
-    compression_flag: &str,
-    provides_hint: &[String],
-) -> Result<Vec<String>> {
-    // List contents
-    let output = Command::new("tar")
-        .args(&[&format!("-t{}f", compression_flag), archive_path])
-        .output()?;
+    let matching: Vec<&Asset> = release
+        .assets
+        .iter()
+        .filter(|asset| matcher.is_match(&asset.name))
+        .collect();
 
-    if !output.status.success() {
-        anyhow::bail!("Failed to list tar contents");
+    if matching.is_empty() {
+        anyhow::bail!(
+            "No assets matching {} in release {} of {}",
+            pattern,
+            release.tag_name,
+            repo
+        );
     }
 
-    let contents = String::from_utf8_lossy(&output.stdout);
-
-    // Find all executables, using hints if available
-    let executables = find_all_executables(&contents, tool_name, provides_hint)?;
-
-    if executables.is_empty() {
-        anyhow::bail!("No executables found in archive");
+    if pinned_sha256.is_some() && matching.len() > 1 {
+        anyhow::bail!(
+            "A pinned sha256 needs a pattern that matches a single asset, but {} matched {}",
+            pattern,
+            matching.len()
+        );
     }
 
-    println!(
-        "  Found executables: {}",
-        executables
-            .iter()
-            .map(|e| e.name.as_str())
-            .collect::<Vec<&str>>()
-            .join(", ")
-    );
-
-    // Extract to temp dir
-    let temp_dir = format!("/tmp/forge-extract-{}", std::process::id());
-    std::fs::create_dir_all(&temp_dir)?;
-
-    Command::new("tar")
-        .args(&[
-            &format!("-x{}f", compression_flag),
-            archive_path,
-            "-C",
-            &temp_dir,
-        ])
-        .status()?;
+    std::fs::create_dir_all(install_dir)?;
+
+    let mut executables = Vec::new();
+    let mut downloads = Vec::new();
+    for asset in matching {
+        let dest = install_dir.join(&asset.name);
+        outln!("  Downloading {}", asset.name);
+        let expected = expected_digests(
+            &asset.name,
+            checksum_url(&release.assets, &asset.name).as_deref(),
+            pinned_sha256,
+            github,
+        )?;
 
-    // Install each executable
-    let mut installed = Vec::new();
-    for exe in executables {
-        let source = Path::new(&temp_dir).join(&exe.path);
-        let dest = install_dir.join(&exe.name);
+        let download = tempfile::NamedTempFile::new_in(install_dir)?;
+        github.download(&asset.browser_download_url, download.path())?;
+        let sha256 = verify_download(download.path(), &asset.name, &expected)?;
+        download.persist(&dest)?;
+        make_executable(&dest)?;
+        executables.push(asset.name.clone());
+        downloads.push(Download {
+            asset: asset.name.clone(),
+            url: asset.browser_download_url.clone(),
+            sha256: Some(sha256),
+        });
+    }
 
-        std::fs::copy(&source, &dest)?;
+    Ok(ReleaseDownload {
+        version: release.tag_name.trim_start_matches('v').to_string(),
+        tag: release.tag_name.clone(),
+        executables,
+        downloads,
+    })
+}
 
-        // Make executable
-        Command::new("chmod")
-            .args(&["+x", dest.to_str().unwrap()])
-            .status()?;
+/// Download assets recorded in a lockfile as-is into `install_dir`, each
+/// checked against its recorded digest
+pub fn download_locked(
+    assets: &[Download],
+    install_dir: &Path,
+    github: &GitHubClient,
+) -> Result<Vec<String>> {
+    std::fs::create_dir_all(install_dir)?;
+
+    let mut executables = Vec::new();
+    for asset in assets {
+        let sha256 = asset
+            .sha256
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("No sha256 recorded for {}", asset.asset))?;
+        outln!("  Downloading {}", asset.asset);
+
+        let download = tempfile::NamedTempFile::new_in(install_dir)?;
+        github.download(&asset.url, download.path())?;
+        verify_download(
+            download.path(),
+            &asset.asset,
+            &[("forge.lock".to_string(), sha256.to_string())],
+        )?;
 
-        installed.push(exe.name);
+        let dest = install_dir.join(&asset.asset);
+        download.persist(&dest)?;
+        make_executable(&dest)?;
+        executables.push(asset.asset.clone());
     }
 
-    // Clean up
-    std::fs::remove_dir_all(&temp_dir).ok();
+    Ok(executables)
+}
 
-    Ok(installed)
+fn glob_to_regex(pattern: &str) -> Result<regex::Regex> {
+    let escaped: Vec<String> = pattern.split('*').map(regex::escape).collect();
+    Ok(regex::Regex::new(&format!("^{}$", escaped.join(".*")))?)
 }
 
-fn extract_zip(
-    archive_path: &str,
+fn install_executables(
+    extract_dir: &Path,
     tool_name: &str,
     install_dir: &Path,
     provides_hint: &[String],
 ) -> Result<Vec<String>> {
-    // List contents
-    let output = Command::new("unzip").args(&["-l", archive_path]).output()?;
-
-    if !output.status.success() {
-        anyhow::bail!("Failed to list zip contents");
-    }
-
-    let contents = String::from_utf8_lossy(&output.stdout);
-    let executables = find_all_executables(&contents, tool_name, provides_hint)?;
+    let candidates: Vec<ExecutableInfo> = archive::find_executables(extract_dir)?
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            Some(ExecutableInfo { name, path })
+        })
+        .collect();
 
-    if executables.is_empty() {
-        anyhow::bail!("No executables found in archive");
-    }
+    // Find all executables, using hints if available
+    let executables = find_all_executables(candidates, tool_name, provides_hint)?;
 
-    println!(
+    outln!(
         "  Found executables: {}",
         executables
             .iter()
@@ -396,92 +582,40 @@ fn extract_zip(
             .join(", ")
     );
 
-    // Extract to temp dir
-    let temp_dir = format!("/tmp/forge-extract-{}", std::process::id());
-    std::fs::create_dir_all(&temp_dir)?;
-
-    Command::new("unzip")
-        .args(&["-q", archive_path, "-d", &temp_dir])
-        .status()?;
-
     // Install each executable
     let mut installed = Vec::new();
     for exe in executables {
-        let source = Path::new(&temp_dir).join(&exe.path);
+        let source = extract_dir.join(&exe.path);
         let dest = install_dir.join(&exe.name);
 
         std::fs::copy(&source, &dest)?;
-
-        // Make executable
-        Command::new("chmod")
-            .args(&["+x", dest.to_str().unwrap()])
-            .status()?;
+        make_executable(&dest)?;
 
         installed.push(exe.name);
     }
 
-    // Clean up
-    std::fs::remove_dir_all(&temp_dir).ok();
-
     Ok(installed)
 }
 
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o755);
+    std::fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+
 fn find_all_executables(
-    contents: &str,
+    mut candidates: Vec<ExecutableInfo>,
     tool_name: &str,
     provides_hint: &[String],
 ) -> Result<Vec<ExecutableInfo>> {
-    let mut candidates = Vec::new();
-
-    // First pass: collect all potential executables
-    for line in contents.lines() {
-        if line.trim().is_empty() || line.ends_with('/') {
-            continue;
-        }
-
-        let path = line.trim();
-        let file_path = Path::new(path);
-
-        if let Some(name) = file_path.file_name().and_then(|n| n.to_str()) {
-            // Skip non-executables
-            if name.starts_with('.')
-                || name.ends_with(".md")
-                || name.ends_with(".txt")
-                || name.ends_with(".1")
-                || name.ends_with(".fish")
-                || name.ends_with(".bash")
-                || name.ends_with(".zsh")
-                || name.ends_with(".ps1")
-                || path.contains("/doc/")
-                || path.contains("/docs/")
-                || path.contains("/complete/")
-                || path.contains("/completion")
-                || name.to_lowercase() == "license"
-                || name.to_lowercase() == "copying"
-                || name.to_lowercase() == "unlicense"
-                || name.to_lowercase() == "readme"
-                || name.to_lowercase().starts_with("license")
-                || name.to_lowercase().starts_with("changelog")
-                || name.to_lowercase().starts_with("authors")
-            {
-                continue;
-            }
-
-            // Look for executable patterns
-            if !name.contains('.') || name.ends_with(".exe") {
-                // Check depth and location
-                let depth = file_path.components().count();
-                if depth <= 3 && !path.contains("/test") {
-                    candidates.push(ExecutableInfo {
-                        name: name.to_string(),
-                        path: path.to_string(),
-                    });
-                }
-            }
-        }
+    if candidates.is_empty() {
+        anyhow::bail!("No executables found in archive");
     }
 
-    // Second pass: prioritize based on hints and heuristics
+    // Prioritize based on hints and heuristics
     let mut selected = Vec::new();
 
     // If we have hints, try to find those first
@@ -522,7 +656,7 @@ fn find_all_executables(
         // Show what we found for debugging
         let all_files: Vec<String> = candidates
             .iter()
-            .map(|c| format!("  {} ({})", c.name, c.path))
+            .map(|c| format!("  {} ({})", c.name, c.path.display()))
             .collect();
 
         anyhow::bail!(
@@ -0,0 +1,133 @@
+use crate::facts::ToolFact;
+use crate::paths::Paths;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// How many earlier versions are kept per tool
+pub const MAX_ENTRIES: usize = 5;
+
+/// Versions a tool was at before it was replaced, oldest first
+/// (`~/.forge/history/<tool>/history.toml`)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct History {
+    #[serde(default)]
+    pub entries: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HistoryEntry {
+    pub fact: ToolFact,
+    /// Copies of the executables it had, keyed by where they were installed.
+    /// Package-manager installs keep only the version.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub saved: BTreeMap<PathBuf, PathBuf>,
+}
+
+impl History {
+    pub fn load(paths: &Paths, tool_name: &str) -> Result<Self> {
+        let file = paths.history_dir(tool_name).join("history.toml");
+        if !file.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", file.display()))
+    }
+
+    pub fn save(&self, paths: &Paths, tool_name: &str) -> Result<()> {
+        let dir = paths.history_dir(tool_name);
+        if self.entries.is_empty() {
+            if dir.exists() {
+                std::fs::remove_dir_all(&dir)?;
+            }
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("history.toml"), toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Remember `fact` as the version being replaced, copying each
+    /// `(installed path, current copy)` pair of its executables
+    pub fn record(
+        paths: &Paths,
+        tool_name: &str,
+        fact: &ToolFact,
+        files: &[(PathBuf, PathBuf)],
+    ) -> Result<()> {
+        let mut history = Self::load(paths, tool_name)?;
+
+        // The same version again replaces its earlier entry
+        if let Some(index) = history.entries.iter().position(|entry| {
+            entry.fact.installer == fact.installer && entry.fact.version == fact.version
+        }) {
+            let entry = history.entries.remove(index);
+            remove_saved(&entry);
+        }
+
+        let version = fact.version.as_deref().unwrap_or("unknown");
+        let entry_dir =
+            paths
+                .history_dir(tool_name)
+                .join(format!("{}-{}", fact.installer, sanitize(version)));
+        let mut saved = BTreeMap::new();
+        for (installed, copy) in files {
+            let Some(name) = installed.file_name() else {
+                continue;
+            };
+            std::fs::create_dir_all(&entry_dir)?;
+            let kept = entry_dir.join(name);
+            std::fs::copy(copy, &kept)
+                .with_context(|| format!("Failed to keep a copy of {}", installed.display()))?;
+            saved.insert(installed.clone(), kept);
+        }
+
+        history.entries.push(HistoryEntry {
+            fact: fact.clone(),
+            saved,
+        });
+        while history.entries.len() > MAX_ENTRIES {
+            let oldest = history.entries.remove(0);
+            remove_saved(&oldest);
+        }
+        history.save(paths, tool_name)
+    }
+
+    pub fn latest(&self) -> Option<&HistoryEntry> {
+        self.entries.last()
+    }
+
+    /// Forget the latest entry along with its saved executables
+    pub fn pop(&mut self) -> Option<HistoryEntry> {
+        let entry = self.entries.pop()?;
+        remove_saved(&entry);
+        Some(entry)
+    }
+}
+
+fn remove_saved(entry: &HistoryEntry) {
+    for kept in entry.saved.values() {
+        std::fs::remove_file(kept).ok();
+    }
+    if let Some(dir) = entry.saved.values().next().and_then(|p| p.parent()) {
+        std::fs::remove_dir(dir).ok();
+    }
+}
+
+/// Versions become directory names
+fn sanitize(version: &str) -> String {
+    version
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
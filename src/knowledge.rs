@@ -1,6 +1,8 @@
+use crate::paths::Paths;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Knowledge {
@@ -33,10 +35,27 @@ pub struct Installer {
     pub installer_type: String,
     pub check: Option<Vec<String>>,
     pub install: Vec<String>,
+    /// Install command for a specific `{version}`
+    pub install_version: Option<Vec<String>>,
     pub uninstall: Option<Vec<String>>,
     pub update: Option<Vec<String>>, // NEW
     pub install_output_pattern: Option<String>,
     pub version_check: Option<VersionCheck>,
+    /// Takes a system-wide lock, so its installs run one at a time
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub exclusive: bool,
+}
+
+impl Installer {
+    /// The install command, or its version-specific form when a version is requested
+    pub fn install_template(&self, version: Option<&str>) -> Result<&[String]> {
+        match version {
+            None => Ok(&self.install),
+            Some(version) => self.install_version.as_deref().ok_or_else(|| {
+                anyhow::anyhow!("This installer cannot install version {}", version)
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -54,6 +73,8 @@ pub struct ToolInstaller {
     pub repo: Option<String>,
     pub pattern: Option<String>,
     pub url: Option<String>,
+    /// Expected sha256 of the downloaded release asset
+    pub sha256: Option<String>,
 
     // For script installers - platform specific
     pub linux: Option<PlatformScripts>,
@@ -78,30 +99,36 @@ pub struct VersionCheck {
 
 impl Knowledge {
     pub async fn load() -> Result<Self> {
+        Self::load_from(&Paths::from_home_dir()?).await
+    }
+
+    pub async fn load_from(paths: &Paths) -> Result<Self> {
         // Load embedded knowledge
         let bundled = include_str!("../data/forge.toml");
         let mut knowledge: Knowledge = toml::from_str(bundled)?;
         knowledge.local_tools = HashSet::new(); // Initialize the field
 
         // Try to load and merge local overlay
-        if let Some(local) = Self::load_local().await? {
+        if let Some(local) = Self::load_local(&paths.local_knowledge()).await? {
             knowledge.merge_local(local);
         }
 
         Ok(knowledge)
     }
 
-    async fn load_local() -> Result<Option<LocalKnowledge>> {
-        let path = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("No home directory"))?
-            .join(".forge")
-            .join("forge.toml");
+    /// Parse a local knowledge file, reporting why it would be ignored
+    pub fn check_local(path: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str::<LocalKnowledge>(&content)?;
+        Ok(())
+    }
 
+    async fn load_local(path: &Path) -> Result<Option<LocalKnowledge>> {
         if !path.exists() {
             return Ok(None);
         }
 
-        match tokio::fs::read_to_string(&path).await {
+        match tokio::fs::read_to_string(path).await {
             Ok(content) => match toml::from_str::<LocalKnowledge>(&content) {
                 Ok(local) => Ok(Some(local)),
                 Err(e) => {
@@ -118,18 +145,18 @@ impl Knowledge {
         }
     }
 
-        fn merge_local(&mut self, local: LocalKnowledge) {
+    fn merge_local(&mut self, local: LocalKnowledge) {
         // Merge tools - local completely replaces embedded
         for (name, tool) in local.tools {
             self.local_tools.insert(name.clone());
             self.tools.insert(name, tool);
         }
-        
+
         // Merge installers - local completely replaces embedded
         for (name, installer) in local.installers {
             self.installers.insert(name, installer);
         }
-        
+
         // Merge platforms - local completely replaces embedded
         for (name, platform) in local.platforms {
             self.platforms.insert(name, platform);
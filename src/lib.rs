@@ -1,11 +1,26 @@
+pub mod archive;
 pub mod backend;
+pub mod backup;
+pub mod checksum;
 pub mod color;
 pub mod command;
+pub mod doctor;
 pub mod facts;
 pub mod forge;
 pub mod format;
 pub mod github;
+pub mod history;
 pub mod knowledge;
+pub mod lock;
+pub mod output;
+pub mod paths;
 pub mod platform;
+pub mod project;
+pub mod report;
+pub mod search;
+pub mod staging;
 pub mod sync;
+pub mod updates;
+pub mod vercmp;
+pub mod verify;
 pub mod version;
@@ -0,0 +1,114 @@
+use crate::facts::{Download, ToolFact};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub const LOCK_FILE: &str = "forge.lock";
+
+/// Exact artifacts resolved for a project, per tool and platform triple
+///
+/// ```toml
+/// [tools.ripgrep.x86_64-unknown-linux-gnu]
+/// installer = "github"
+/// version = "14.1.1"
+/// tag = "14.1.1"
+///
+/// [[tools.ripgrep.x86_64-unknown-linux-gnu.assets]]
+/// asset = "ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz"
+/// url = "https://github.com/..."
+/// sha256 = "..."
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    pub tools: BTreeMap<String, BTreeMap<String, LockedTool>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LockedTool {
+    pub installer: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<Download>,
+}
+
+impl LockedTool {
+    /// Lock what an installed tool resolved to, if its version is known
+    pub fn from_fact(fact: &ToolFact) -> Option<Self> {
+        let version = fact.version.clone()?;
+        let (tag, assets) = match &fact.provenance {
+            Some(provenance) => (provenance.tag.clone(), provenance.downloads.clone()),
+            None => (None, Vec::new()),
+        };
+
+        Some(Self {
+            installer: fact.installer.clone(),
+            version,
+            tag,
+            assets,
+        })
+    }
+
+    /// Whether an installed tool is exactly what this entry pins
+    pub fn matches(&self, fact: &ToolFact) -> bool {
+        if fact.installer != self.installer || fact.version.as_deref() != Some(&self.version) {
+            return false;
+        }
+
+        // Release downloads must also be the same files
+        if self.assets.is_empty() {
+            return true;
+        }
+        let installed: &[Download] = fact
+            .provenance
+            .as_ref()
+            .map_or(&[], |p| p.downloads.as_slice());
+        self.assets.len() == installed.len()
+            && self.assets.iter().all(|locked| {
+                installed
+                    .iter()
+                    .any(|d| d.asset == locked.asset && d.sha256 == locked.sha256)
+            })
+    }
+}
+
+impl Lockfile {
+    /// Read a lockfile, treating a missing file as empty
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = format!(
+            "# Generated by forge apply. Commit this file; do not edit by hand.\n\n{}",
+            toml::to_string_pretty(self)?
+        );
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn get(&self, tool: &str, triple: &str) -> Option<&LockedTool> {
+        self.tools.get(tool)?.get(triple)
+    }
+
+    pub fn insert(&mut self, tool: &str, triple: &str, locked: LockedTool) {
+        self.tools
+            .entry(tool.to_string())
+            .or_default()
+            .insert(triple.to_string(), locked);
+    }
+
+    /// Drop tools a project no longer lists
+    pub fn retain_tools(&mut self, tools: &[String]) {
+        self.tools.retain(|name, _| tools.contains(name));
+    }
+}
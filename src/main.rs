@@ -1,6 +1,16 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use forge::forge::Forge;
+use forge::forge::{DEFAULT_JOBS, Forge};
+use forge::report::{UpdateList, print_json};
+use forge::updates::UpdateStatus;
+
+/// Exit code of `forge outdated` when updates are available, distinct from
+/// the 1 of a failed command
+const EXIT_OUTDATED: i32 = 10;
+
+/// Exit code of `forge outdated` when a tool's latest version could not be
+/// looked up, so a CI job can't mistake it for "up to date"
+const EXIT_CHECK_FAILED: i32 = 11;
 
 #[derive(Parser)]
 #[command(name = "forge")]
@@ -12,17 +22,27 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Install a tool
+    /// Install one or more tools
     Install {
-        /// Name of the tool
-        tool: String,
+        /// Names of the tools, optionally with a version (tool@1.2.3)
+        #[arg(required = true)]
+        tools: Vec<String>,
 
         /// Specific installer to use
         #[arg(long, short = 'i')]
         installer: Option<String>,
+
+        /// Show what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// How many version checks and installs to run at once
+        #[arg(long, short = 'j', default_value_t = DEFAULT_JOBS)]
+        jobs: usize,
     },
 
-    /// Update installed tools
+    /// Update installed tools. See `forge outdated --json` for the version
+    /// checks as JSON.
     Update {
         /// Name of specific tool to update (updates all if not specified)
         tool: Option<String>,
@@ -30,22 +50,164 @@ enum Commands {
         /// Skip updating package managers/installers
         #[arg(long)]
         tools_only: bool,
+
+        /// Show what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// How many version checks and installs to run at once
+        #[arg(long, short = 'j', default_value_t = DEFAULT_JOBS)]
+        jobs: usize,
+    },
+
+    /// List tools with newer versions available, without changing anything.
+    /// Exits with code 11 when any could not be checked, otherwise 10 when
+    /// any can be updated.
+    Outdated {
+        /// Name of a specific tool to check (checks all if not specified)
+        tool: Option<String>,
+
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// How many version checks to run at once
+        #[arg(long, short = 'j', default_value_t = DEFAULT_JOBS)]
+        jobs: usize,
     },
 
     /// Uninstall a tool
     Uninstall {
         /// Name of the tool
         tool: String,
+
+        /// Show what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Explain why a tool exists
     Why {
         /// Name of the tool
         tool: String,
+
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show everything known about a tool
+    Info {
+        /// Name of the tool
+        tool: String,
+    },
+
+    /// Show which tools provide an executable and which binary runs for it
+    Which {
+        /// Name of the executable
+        executable: String,
+    },
+
+    /// Find tools by name, description, executable or package name
+    Search {
+        /// Words to look for
+        #[arg(required = true)]
+        query: Vec<String>,
+    },
+
+    /// Show how a tool would be installed, without installing it
+    Plan {
+        /// Name of the tool, optionally with a version (tool@1.2.3)
+        tool: String,
+
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Restore the version a tool was at before its last install or update
+    Rollback {
+        /// Name of the tool
+        tool: String,
+
+        /// Show what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Hold a tool back from updates
+    Pin {
+        /// Name of the tool
+        tool: String,
+
+        /// Allow updates within this version prefix, such as 1.28
+        range: Option<String>,
+
+        /// Show what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Let a pinned tool be updated again
+    Unpin {
+        /// Name of the tool
+        tool: String,
+
+        /// Show what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// List installed tools
-    List,
+    List {
+        /// Show where each tool was installed from
+        #[arg(long, short = 'v')]
+        verbose: bool,
+
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check forge-installed executables against the hashes recorded at install
+    Verify {
+        /// Name of a specific tool to verify (verifies all if not specified)
+        tool: Option<String>,
+
+        /// Reinstall tools whose executables were modified, replaced or deleted
+        #[arg(long)]
+        restore: bool,
+
+        /// Show what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Install the tools listed in forge.project.toml
+    Apply {
+        /// Path to the project manifest (searches upward from the current directory if not specified)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Report whether every project tool is installed, without installing
+        #[arg(long)]
+        check: bool,
+
+        /// Install exactly the versions and assets recorded in forge.lock
+        #[arg(long)]
+        locked: bool,
+
+        /// Show what would happen without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// How many version checks and installs to run at once
+        #[arg(long, short = 'j', default_value_t = DEFAULT_JOBS)]
+        jobs: usize,
+    },
+
+    /// Check the environment and forge's state, suggesting fixes
+    Doctor,
 
     /// Format TOML files
     Fmt {
@@ -82,26 +244,125 @@ enum Commands {
     },
 }
 
+impl Commands {
+    /// Only commands that honor `--dry-run` accept it
+    fn dry_run(&self) -> bool {
+        match self {
+            Commands::Install { dry_run, .. }
+            | Commands::Update { dry_run, .. }
+            | Commands::Uninstall { dry_run, .. }
+            | Commands::Rollback { dry_run, .. }
+            | Commands::Pin { dry_run, .. }
+            | Commands::Unpin { dry_run, .. }
+            | Commands::Verify { dry_run, .. }
+            | Commands::Apply { dry_run, .. } => *dry_run,
+            _ => false,
+        }
+    }
+
+    /// Only commands that run jobs side by side accept `--jobs`
+    fn jobs(&self) -> usize {
+        match self {
+            Commands::Install { jobs, .. }
+            | Commands::Update { jobs, .. }
+            | Commands::Outdated { jobs, .. }
+            | Commands::Apply { jobs, .. } => *jobs,
+            _ => DEFAULT_JOBS,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
-    let forge = Forge::new().await?;
+    let forge = Forge::new()
+        .await?
+        .dry_run(cli.command.dry_run())
+        .jobs(cli.command.jobs());
 
     match cli.command {
-        Commands::Install { tool, installer } => {
-            forge.install(&tool, installer.as_deref()).await?;
+        Commands::Install {
+            tools, installer, ..
+        } => {
+            forge.install_all(&tools, installer.as_deref()).await?;
         }
-        Commands::Update { tool, tools_only } => {
+        Commands::Update {
+            tool, tools_only, ..
+        } => {
             forge.update(tool.as_deref(), tools_only).await?;
         }
-        Commands::Uninstall { tool } => {
+        Commands::Outdated { tool, json, .. } => {
+            let checks = if json {
+                let list = UpdateList {
+                    tools: forge.update_checks(tool.as_deref()).await?,
+                };
+                print_json(&list)?;
+                list.tools
+            } else {
+                forge.outdated(tool.as_deref()).await?
+            };
+            if checks.iter().any(|c| c.status == UpdateStatus::CheckFailed) {
+                std::process::exit(EXIT_CHECK_FAILED);
+            }
+            if checks.iter().any(|c| c.status == UpdateStatus::Available) {
+                std::process::exit(EXIT_OUTDATED);
+            }
+        }
+        Commands::Uninstall { tool, .. } => {
             forge.uninstall(&tool).await?;
         }
-        Commands::Why { tool } => {
-            forge.why(&tool)?;
+        Commands::Why { tool, json } => {
+            if json {
+                print_json(&forge.tool_info(&tool).await?)?;
+            } else {
+                forge.why(&tool)?;
+            }
+        }
+        Commands::Info { tool } => {
+            forge.info(&tool).await?;
+        }
+        Commands::Which { executable } => {
+            forge.which(&executable).await?;
+        }
+        Commands::Search { query } => {
+            forge.search(&query.join(" ")).await?;
+        }
+        Commands::Plan { tool, json } => {
+            if json {
+                print_json(&forge.install_plan(&tool).await?)?;
+            } else {
+                forge.plan(&tool).await?;
+            }
+        }
+        Commands::Rollback { tool, .. } => {
+            forge.rollback(&tool).await?;
+        }
+        Commands::Pin { tool, range, .. } => {
+            forge.pin(&tool, range.as_deref()).await?;
+        }
+        Commands::Unpin { tool, .. } => {
+            forge.unpin(&tool).await?;
+        }
+        Commands::List { verbose, json } => {
+            if json {
+                print_json(&forge.installed_tools().await?)?;
+            } else {
+                forge.list(verbose).await?;
+            }
+        }
+        Commands::Verify { tool, restore, .. } => {
+            forge.verify(tool.as_deref(), restore).await?;
+        }
+        Commands::Apply {
+            file,
+            check,
+            locked,
+            ..
+        } => {
+            forge.apply(file.as_deref(), check, locked).await?;
         }
-        Commands::List => {
-            forge.list().await?;
+        Commands::Doctor => {
+            forge.doctor().await?;
         }
         Commands::Fmt { file, check } => {
             forge.fmt(file.as_deref(), check).await?;
@@ -0,0 +1,50 @@
+//! Progress output that a parallel job can hold back and print in one piece,
+//! so lines from tools installing side by side don't interleave
+
+use std::cell::RefCell;
+use std::fmt::Write;
+
+thread_local! {
+    static CAPTURE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Print a line, or add it to this thread's buffer while it is capturing
+pub fn line(args: std::fmt::Arguments) {
+    CAPTURE.with(|capture| match capture.borrow_mut().as_mut() {
+        Some(buffer) => {
+            let _ = writeln!(buffer, "{}", args);
+        }
+        None => println!("{}", args),
+    })
+}
+
+/// Run `f`, collecting the lines it prints on this thread instead of
+/// printing them
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    /// Stops capturing even if `f` panics, as threads are reused
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            CAPTURE.with(|capture| capture.borrow_mut().take());
+        }
+    }
+
+    CAPTURE.with(|capture| *capture.borrow_mut() = Some(String::new()));
+    let reset = Reset;
+    let value = f();
+    let output = CAPTURE.with(|capture| capture.borrow_mut().take());
+    drop(reset);
+    (value, output.unwrap_or_default())
+}
+
+/// `println!` that goes through [`line`]
+macro_rules! outln {
+    () => {
+        $crate::output::line(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::line(format_args!($($arg)*))
+    };
+}
+
+pub(crate) use outln;
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Filesystem locations forge reads and writes, rooted at a home directory
+#[derive(Debug, Clone)]
+pub struct Paths {
+    home: PathBuf,
+    bin_dir: PathBuf,
+    search_path: Option<OsString>,
+}
+
+impl Paths {
+    /// Locations under the current user's home directory
+    pub fn from_home_dir() -> Result<Self> {
+        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("No home directory"))?;
+        Ok(Self::new(home))
+    }
+
+    /// Locations under an arbitrary root, e.g. a temp dir in tests
+    pub fn new(home: impl Into<PathBuf>) -> Self {
+        let home = home.into();
+        let bin_dir = home.join(".local").join("bin");
+        Self {
+            home,
+            bin_dir,
+            search_path: None,
+        }
+    }
+
+    /// Install executables somewhere other than `<home>/.local/bin`
+    pub fn with_bin_dir(mut self, bin_dir: impl Into<PathBuf>) -> Self {
+        self.bin_dir = bin_dir.into();
+        self
+    }
+
+    /// Resolve executables against this PATH instead of the process's
+    pub fn with_search_path(mut self, search_path: impl Into<OsString>) -> Self {
+        self.search_path = Some(search_path.into());
+        self
+    }
+
+    /// PATH used to find which binary a command name resolves to
+    pub fn search_path(&self) -> OsString {
+        self.search_path
+            .clone()
+            .or_else(|| std::env::var_os("PATH"))
+            .unwrap_or_default()
+    }
+
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    /// Where installed executables are placed (`~/.local/bin`)
+    pub fn bin_dir(&self) -> &Path {
+        &self.bin_dir
+    }
+
+    /// Forge's own state directory (`~/.forge`)
+    pub fn forge_dir(&self) -> PathBuf {
+        self.home.join(".forge")
+    }
+
+    /// Record of installed tools (`~/.forge/facts.toml`)
+    pub fn facts_file(&self) -> PathBuf {
+        self.forge_dir().join("facts.toml")
+    }
+
+    /// Copies of files an install is replacing, kept until it succeeds
+    /// (`~/.forge/backup`)
+    pub fn backup_dir(&self) -> PathBuf {
+        self.forge_dir().join("backup")
+    }
+
+    /// Downloads waiting to be moved into the bin dir (`~/.forge/staging`)
+    pub fn staging_dir(&self) -> PathBuf {
+        self.forge_dir().join("staging")
+    }
+
+    /// Earlier versions of a tool, for rollback (`~/.forge/history/<tool>`)
+    pub fn history_dir(&self, tool_name: &str) -> PathBuf {
+        self.forge_dir().join("history").join(tool_name)
+    }
+
+    /// Local knowledge overlay (`~/.forge/forge.toml`)
+    pub fn local_knowledge(&self) -> PathBuf {
+        self.forge_dir().join("forge.toml")
+    }
+}
@@ -11,7 +11,7 @@ impl Platform {
     pub fn detect() -> Result<Self> {
         let os = match env::consts::OS {
             "linux" => "linux",
-            "macos" | "darwin" => "macos", 
+            "macos" | "darwin" => "macos",
             "windows" => "windows",
             other => anyhow::bail!("Unsupported OS: {}", other),
         };
@@ -27,21 +27,23 @@ impl Platform {
             arch: arch.to_string(),
         })
     }
-    
+
     pub fn expand_pattern(&self, pattern: &str) -> String {
         pattern
             .replace("{os}", &self.os)
             .replace("{arch}", &self.arch)
             .replace("{target}", &self.target_triple())
     }
-    
-    fn target_triple(&self) -> String {
+
+    /// Rust-style target triple naming this platform, e.g. `x86_64-unknown-linux-gnu`
+    pub fn target_triple(&self) -> String {
         match (self.os.as_str(), self.arch.as_str()) {
             ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
             ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
             ("macos", "x86_64") => "x86_64-apple-darwin",
             ("macos", "aarch64") => "aarch64-apple-darwin",
             _ => "unknown",
-        }.to_string()
+        }
+        .to_string()
     }
 }
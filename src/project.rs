@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const PROJECT_FILE: &str = "forge.project.toml";
+
+/// Tools a repository needs, read from `forge.project.toml`
+///
+/// ```toml
+/// tools = ["ripgrep", "uv"]
+///
+/// [installers]
+/// uv = "github"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectManifest {
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// Preferred installer per tool, used when the tool is missing
+    #[serde(default)]
+    pub installers: BTreeMap<String, String>,
+}
+
+impl ProjectManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let manifest: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        for tool in manifest.installers.keys() {
+            if !manifest.tools.contains(tool) {
+                anyhow::bail!(
+                    "{} sets an installer for {}, which is not in its tools list",
+                    path.display(),
+                    tool
+                );
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Look for `forge.project.toml` in `start` and then each parent directory
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(PROJECT_FILE))
+            .find(|path| path.is_file())
+    }
+
+    pub fn installer_for(&self, tool: &str) -> Option<&str> {
+        self.installers.get(tool).map(String::as_str)
+    }
+}
@@ -0,0 +1,157 @@
+//! Documents printed by `--json`. Fields are only ever added; removing or
+//! changing the meaning of one bumps `SCHEMA_VERSION`.
+
+use crate::facts::ToolFact;
+use crate::updates::UpdateCheck;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Document<'a, T> {
+    schema_version: u32,
+    #[serde(flatten)]
+    body: &'a T,
+}
+
+/// `body` as a pretty JSON object tagged with the schema version
+pub fn to_json<T: Serialize>(body: &T) -> Result<String> {
+    let document = Document {
+        schema_version: SCHEMA_VERSION,
+        body,
+    };
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+pub fn print_json<T: Serialize>(body: &T) -> Result<()> {
+    println!("{}", to_json(body)?);
+    Ok(())
+}
+
+/// `forge list --json`
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolList {
+    pub tools: Vec<InstalledTool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledTool {
+    pub name: String,
+    /// None once the knowledge base no longer describes the tool
+    pub description: Option<String>,
+    /// Defined in the local knowledge overlay rather than the bundled one
+    pub local: bool,
+    #[serde(flatten)]
+    pub installation: Installation,
+}
+
+/// What facts.toml records about an installed tool
+#[derive(Debug, Clone, Serialize)]
+pub struct Installation {
+    pub installer: String,
+    pub version: Option<String>,
+    pub installed_at: DateTime<Utc>,
+    pub pin: Option<PinInfo>,
+    /// Where each executable was installed, by name
+    pub executables: BTreeMap<String, PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PinInfo {
+    /// Version prefix updates may stay within; none holds the tool in place
+    pub range: Option<String>,
+}
+
+impl From<&ToolFact> for Installation {
+    fn from(fact: &ToolFact) -> Self {
+        Self {
+            installer: fact.installer.clone(),
+            version: fact.version.clone(),
+            installed_at: fact.installed_at,
+            pin: fact.pin.as_ref().map(|pin| PinInfo {
+                range: pin.range.clone(),
+            }),
+            executables: fact
+                .provenance
+                .iter()
+                .flat_map(|p| &p.executables)
+                .map(|(name, record)| (name.clone(), record.path.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// `forge why --json`
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub description: String,
+    pub provides: Vec<String>,
+    /// Every installer the tool declares, sorted
+    pub installers: Vec<String>,
+    pub local: bool,
+    pub installed: Option<Installation>,
+}
+
+/// `forge outdated --json`
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateList {
+    pub tools: Vec<UpdateCheck>,
+}
+
+/// `forge plan --json`
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanReport {
+    pub name: String,
+    /// The version asked for with `tool@version`
+    pub version: Option<String>,
+    pub os: String,
+    pub arch: String,
+    pub precedence: Vec<String>,
+    pub installed: Option<Installation>,
+    /// Each installer in precedence order, then those this platform ignores
+    pub candidates: Vec<Candidate>,
+    pub selected: Option<String>,
+    pub action: Option<PlannedAction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    pub installer: String,
+    pub status: CandidateStatus,
+    /// Why an installer is unavailable
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateStatus {
+    Selected,
+    Available,
+    Unavailable,
+    /// In the platform precedence, but the tool has no entry for it
+    NotDeclared,
+    /// Declared by the tool, but not in this platform's precedence
+    NotUsed,
+}
+
+/// What the selected installer would run
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlannedAction {
+    Command {
+        command: Vec<String>,
+    },
+    Script {
+        script: String,
+    },
+    GithubRelease {
+        repo: String,
+        pattern: Option<String>,
+        version: Option<String>,
+    },
+}
@@ -0,0 +1,116 @@
+use crate::knowledge::{Knowledge, Tool};
+use std::path::PathBuf;
+
+/// A tool that matched `forge search`, ranked by `score`
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub name: String,
+    pub description: String,
+    pub score: u32,
+    /// Declares an installer this platform uses
+    pub installable: bool,
+    /// Installed version, if the tool is in facts
+    pub installed: Option<String>,
+}
+
+/// Tool names matching every whitespace-separated term of `query`,
+/// case-insensitively, best first
+pub fn rank(knowledge: &Knowledge, query: &str) -> Vec<(String, u32)> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ranked: Vec<(String, u32)> = knowledge
+        .tools
+        .iter()
+        .filter_map(|(name, tool)| {
+            let scores: Vec<u32> = terms.iter().map(|term| score(name, tool, term)).collect();
+            scores
+                .iter()
+                .all(|s| *s > 0)
+                .then(|| (name.clone(), scores.iter().sum()))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+/// How well one term matches a tool. Names count most, then executables it
+/// provides and package names, then the description.
+fn score(name: &str, tool: &Tool, term: &str) -> u32 {
+    let name = name.to_lowercase();
+    let name_score = if name == term {
+        100
+    } else if name.starts_with(term) {
+        60
+    } else if name.contains(term) {
+        40
+    } else {
+        0
+    };
+
+    let provides_score = tool
+        .provides
+        .iter()
+        .map(|exe| exe.to_lowercase())
+        .map(|exe| match exe {
+            _ if exe == term => 80,
+            _ if exe.contains(term) => 30,
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0);
+
+    // GitHub installers name a repo where others name a package
+    let package_score = tool
+        .installers
+        .values()
+        .flat_map(|ti| [ti.package.as_deref(), ti.repo.as_deref()])
+        .flatten()
+        .map(|package| package.to_lowercase())
+        .map(|package| {
+            let last = package.rsplit('/').next().unwrap_or_default();
+            match package {
+                _ if package == term || last == term => 70,
+                _ if package.contains(term) => 25,
+                _ => 0,
+            }
+        })
+        .max()
+        .unwrap_or(0);
+
+    let description_score = if tool.description.to_lowercase().contains(term) {
+        10
+    } else {
+        0
+    };
+
+    [name_score, provides_score, package_score, description_score]
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+}
+
+/// What `forge which` found for an executable
+#[derive(Debug, Clone)]
+pub struct Location {
+    /// Tools whose `provides` lists it, the one named after it first
+    pub providers: Vec<Provider>,
+    /// Every match on PATH, the one that runs first
+    pub on_path: Vec<PathBuf>,
+    /// The provider that installed the binary that runs, if forge did
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Provider {
+    pub name: String,
+    /// Installed version, if the tool is in facts
+    pub installed: Option<String>,
+    pub installer: Option<String>,
+    /// Where the installed tool put the executable
+    pub path: Option<PathBuf>,
+    /// Declares an installer this platform uses
+    pub installable: bool,
+}
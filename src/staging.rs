@@ -0,0 +1,57 @@
+use crate::backup::{Backup, copy_into_place};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A scratch directory where downloads land before any of them replace
+/// what is installed
+#[derive(Debug)]
+pub struct Staging {
+    dir: TempDir,
+}
+
+impl Staging {
+    /// Stage in a fresh directory under `root`, off PATH so half-written
+    /// files are never picked up as commands
+    pub fn new(root: &Path) -> Result<Self> {
+        std::fs::create_dir_all(root)?;
+        let dir = tempfile::Builder::new()
+            .prefix("forge-")
+            .tempdir_in(root)
+            .with_context(|| format!("Failed to create a staging area in {}", root.display()))?;
+        Ok(Self { dir })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Move every staged file into `install_dir`, saving what each one
+    /// replaces in `backup` first
+    pub fn commit(self, install_dir: &Path, backup: &mut Backup) -> Result<()> {
+        let mut staged: Vec<PathBuf> = std::fs::read_dir(self.path())?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<_>>()?;
+        staged.sort();
+
+        let moves: Vec<(PathBuf, PathBuf)> = staged
+            .into_iter()
+            .filter_map(|path| {
+                let dest = install_dir.join(path.file_name()?);
+                Some((path, dest))
+            })
+            .collect();
+
+        for (_, dest) in &moves {
+            backup.save(dest)?;
+        }
+        for (path, dest) in &moves {
+            // A rename unless staging is on another filesystem
+            if std::fs::rename(path, dest).is_err() {
+                copy_into_place(path, dest)
+                    .with_context(|| format!("Failed to move {} into place", dest.display()))?;
+            }
+        }
+        Ok(())
+    }
+}
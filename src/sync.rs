@@ -1,8 +1,8 @@
 use crate::color::{Colors, ERROR};
+use crate::command::CommandRunner;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SyncConfig {
@@ -13,9 +13,9 @@ pub struct SyncConfig {
 }
 
 /// Check if gh CLI is available and authenticated
-pub fn check_gh_auth() -> Result<()> {
+pub fn check_gh_auth(runner: &dyn CommandRunner) -> Result<()> {
     // Check if gh exists
-    let gh_check = Command::new("gh").arg("--version").output();
+    let gh_check = runner.run("gh", &["--version".to_string()]);
 
     if gh_check.is_err() || !gh_check.unwrap().status.success() {
         anyhow::bail!(
@@ -28,7 +28,7 @@ pub fn check_gh_auth() -> Result<()> {
     }
 
     // Check if authenticated
-    let auth_check = Command::new("gh").args(&["auth", "status"]).output()?;
+    let auth_check = runner.run("gh", &["auth", "status"].map(String::from))?;
 
     if !auth_check.status.success() {
         anyhow::bail!(
@@ -44,10 +44,9 @@ pub fn check_gh_auth() -> Result<()> {
 }
 
 /// Get current GitHub username
-pub fn get_github_user() -> Result<String> {
-    let output = Command::new("gh")
-        .args(&["api", "user", "--jq", ".login"])
-        .output()
+pub fn get_github_user(runner: &dyn CommandRunner) -> Result<String> {
+    let output = runner
+        .run("gh", &["api", "user", "--jq", ".login"].map(String::from))
         .context("Failed to get GitHub user")?;
 
     if !output.status.success() {
@@ -93,27 +92,20 @@ pub fn extract_gist_id(url: &str) -> Result<String> {
 }
 
 /// Create a new gist with the given content
-pub fn create_gist(content: &str, filename: &str, private: bool) -> Result<(String, String)> {
+pub fn create_gist(
+    content: &str,
+    filename: &str,
+    private: bool,
+    runner: &dyn CommandRunner,
+) -> Result<(String, String)> {
     let mut args = vec!["gist", "create", "-f", filename, "-"];
     if !private {
         args.push("--public");
     }
     // Note: gists are secret by default, so we only add --public flag
 
-    let mut child = Command::new("gh")
-        .args(&args)
-        .stdin(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()?;
-
-    // Write content to stdin
-    use std::io::Write;
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin.write_all(content.as_bytes())?;
-    }
-
-    let output = child.wait_with_output()?;
+    let args: Vec<String> = args.into_iter().map(String::from).collect();
+    let output = runner.run_with_input("gh", &args, content)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -127,21 +119,17 @@ pub fn create_gist(content: &str, filename: &str, private: bool) -> Result<(Stri
 }
 
 /// Update an existing gist
-pub fn update_gist(gist_id: &str, content: &str, filename: &str) -> Result<()> {
-    let mut child = Command::new("gh")
-        .args(&["gist", "edit", gist_id, "-f", filename, "-"])
-        .stdin(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()?;
-
-    // Write content to stdin
-    use std::io::Write;
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin.write_all(content.as_bytes())?;
-    }
-
-    let output = child.wait_with_output()?;
+pub fn update_gist(
+    gist_id: &str,
+    content: &str,
+    filename: &str,
+    runner: &dyn CommandRunner,
+) -> Result<()> {
+    let output = runner.run_with_input(
+        "gh",
+        &["gist", "edit", gist_id, "-f", filename, "-"].map(String::from),
+        content,
+    )?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -152,10 +140,8 @@ pub fn update_gist(gist_id: &str, content: &str, filename: &str) -> Result<()> {
 }
 
 /// Download gist content
-pub fn download_gist(url: &str) -> Result<String> {
-    let output = Command::new("gh")
-        .args(&["gist", "view", url, "--raw"])
-        .output()?;
+pub fn download_gist(url: &str, runner: &dyn CommandRunner) -> Result<String> {
+    let output = runner.run("gh", &["gist", "view", url, "--raw"].map(String::from))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -0,0 +1,70 @@
+use crate::facts::ToolFact;
+use crate::vercmp::{self, Scheme};
+use serde::Serialize;
+
+/// What a version check found for one installed tool
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCheck {
+    pub name: String,
+    pub installer: String,
+    pub current: Option<String>,
+    pub latest: Option<String>,
+    pub status: UpdateStatus,
+    /// Why the latest version could not be looked up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateStatus {
+    UpToDate,
+    Available,
+    /// Pinned without a range, so the latest version was not looked up
+    Pinned,
+    /// A newer version exists outside the pinned range
+    Held,
+    /// The latest version could not be looked up
+    CheckFailed,
+}
+
+impl UpdateCheck {
+    /// Compare a tool's recorded version against the latest one found.
+    /// Only a strictly newer release counts; a lower one is never offered.
+    pub fn new(name: &str, fact: &ToolFact, latest: Option<String>) -> Self {
+        let has_update = match (&fact.version, &latest) {
+            (Some(current), Some(latest)) => {
+                vercmp::is_newer(latest, current, Scheme::for_installer(&fact.installer))
+            }
+            _ => false,
+        };
+
+        let status = match (&fact.pin, &latest) {
+            (Some(pin), _) if pin.range.is_none() => UpdateStatus::Pinned,
+            (Some(pin), Some(latest)) if has_update && !pin.allows(latest) => UpdateStatus::Held,
+            _ if has_update => UpdateStatus::Available,
+            _ => UpdateStatus::UpToDate,
+        };
+
+        Self {
+            name: name.to_string(),
+            installer: fact.installer.clone(),
+            current: fact.version.clone(),
+            latest,
+            status,
+            error: None,
+        }
+    }
+
+    /// A tool whose latest version could not be looked up
+    pub fn failed(name: &str, fact: &ToolFact, error: &anyhow::Error) -> Self {
+        Self {
+            name: name.to_string(),
+            installer: fact.installer.clone(),
+            current: fact.version.clone(),
+            latest: None,
+            status: UpdateStatus::CheckFailed,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+}
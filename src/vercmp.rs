@@ -0,0 +1,204 @@
+use std::cmp::Ordering;
+
+/// How an installer spells its versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// `1.2.3-rc.1+build`, leniently: a `v` prefix and missing components are fine
+    Semver,
+    /// `epoch:upstream-revision`, ordered the way dpkg orders them
+    Debian,
+    /// A semver-like version with an optional `_revision` suffix
+    Homebrew,
+}
+
+impl Scheme {
+    pub fn for_installer(installer: &str) -> Self {
+        match installer {
+            "apt" => Scheme::Debian,
+            "brew" => Scheme::Homebrew,
+            _ => Scheme::Semver,
+        }
+    }
+}
+
+pub fn compare(a: &str, b: &str, scheme: Scheme) -> Ordering {
+    match scheme {
+        Scheme::Semver => compare_semver(a, b),
+        Scheme::Debian => compare_debian(a, b),
+        Scheme::Homebrew => compare_homebrew(a, b),
+    }
+}
+
+/// Whether `candidate` is strictly newer than `current`, so that moving to
+/// it is an upgrade and never a downgrade
+pub fn is_newer(candidate: &str, current: &str, scheme: Scheme) -> bool {
+    compare(candidate, current, scheme) == Ordering::Greater
+}
+
+fn compare_semver(a: &str, b: &str) -> Ordering {
+    let (a_core, a_pre) = split_semver(a);
+    let (b_core, b_pre) = split_semver(b);
+
+    compare_dotted(a_core, b_core).then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        // A pre-release comes before its release
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(a), Some(b)) => compare_prerelease(a, b),
+    })
+}
+
+/// Core version and pre-release, with any `v` prefix and build metadata dropped
+fn split_semver(version: &str) -> (&str, Option<&str>) {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or(version);
+    match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    }
+}
+
+/// Compare dot-separated components, treating missing ones as zero
+fn compare_dotted(a: &str, b: &str) -> Ordering {
+    let a: Vec<&str> = a.split('.').collect();
+    let b: Vec<&str> = b.split('.').collect();
+
+    for i in 0..a.len().max(b.len()) {
+        let ordering = compare_identifier(
+            a.get(i).copied().unwrap_or("0"),
+            b.get(i).copied().unwrap_or("0"),
+        );
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            // A longer set of identifiers sorts after its prefix
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let ordering = compare_identifier(x, y);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Numbers compare numerically and sort before words, which compare as text
+fn compare_identifier(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(x), Ok(y)) => x.cmp(&y),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// Formula revisions only order versions that both carry one. A bare
+/// `0.24.0` is whatever revision is installed, so `0.24.0_1` equals it.
+fn compare_homebrew(a: &str, b: &str) -> Ordering {
+    let (a_version, a_revision) = split_homebrew(a);
+    let (b_version, b_revision) = split_homebrew(b);
+    compare_semver(a_version, b_version).then(match (a_revision, b_revision) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => Ordering::Equal,
+    })
+}
+
+/// `0.24.0_1` is version `0.24.0` at formula revision 1
+fn split_homebrew(version: &str) -> (&str, Option<u64>) {
+    let version = version.trim();
+    match version.rsplit_once('_') {
+        Some((base, revision)) => match revision.parse() {
+            Ok(revision) => (base, Some(revision)),
+            Err(_) => (version, None),
+        },
+        None => (version, None),
+    }
+}
+
+fn compare_debian(a: &str, b: &str) -> Ordering {
+    let (a_epoch, a_upstream, a_revision) = split_debian(a);
+    let (b_epoch, b_upstream, b_revision) = split_debian(b);
+
+    a_epoch
+        .cmp(&b_epoch)
+        .then_with(|| compare_dpkg_part(a_upstream, b_upstream))
+        .then_with(|| compare_dpkg_part(a_revision, b_revision))
+}
+
+/// `1:2.3-1ubuntu1` is epoch 1, upstream `2.3`, revision `1ubuntu1`
+fn split_debian(version: &str) -> (u64, &str, &str) {
+    let version = version.trim();
+    let (epoch, rest) = match version.split_once(':') {
+        Some((epoch, rest)) if epoch.chars().all(|c| c.is_ascii_digit()) => {
+            (epoch.parse().unwrap_or(0), rest)
+        }
+        _ => (0, version),
+    };
+    match rest.rsplit_once('-') {
+        Some((upstream, revision)) => (epoch, upstream, revision),
+        None => (epoch, rest, ""),
+    }
+}
+
+/// dpkg's `verrevcmp`: alternate non-digit runs, compared with `~` before
+/// everything (even the end of the string) and letters before other
+/// symbols, with digit runs compared as numbers
+fn compare_dpkg_part(a: &str, b: &str) -> Ordering {
+    let mut a = a.as_bytes();
+    let mut b = b.as_bytes();
+
+    while !a.is_empty() || !b.is_empty() {
+        let a_text = a.iter().take_while(|c| !c.is_ascii_digit()).count();
+        let b_text = b.iter().take_while(|c| !c.is_ascii_digit()).count();
+
+        for i in 0..a_text.max(b_text) {
+            let ordering = dpkg_order(a.get(i).filter(|_| i < a_text))
+                .cmp(&dpkg_order(b.get(i).filter(|_| i < b_text)));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        a = &a[a_text..];
+        b = &b[b_text..];
+
+        let a_digits = a.iter().take_while(|c| c.is_ascii_digit()).count();
+        let b_digits = b.iter().take_while(|c| c.is_ascii_digit()).count();
+        let ordering = compare_digits(&a[..a_digits], &b[..b_digits]);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        a = &a[a_digits..];
+        b = &b[b_digits..];
+    }
+    Ordering::Equal
+}
+
+fn dpkg_order(c: Option<&u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(b'~') => -1,
+        Some(c) if c.is_ascii_alphabetic() => *c as i32,
+        Some(c) => *c as i32 + 256,
+    }
+}
+
+/// Compare digit runs numerically without overflowing on long ones
+fn compare_digits(a: &[u8], b: &[u8]) -> Ordering {
+    let strip = |s: &[u8]| -> usize { s.iter().take_while(|c| **c == b'0').count() };
+    let a = &a[strip(a)..];
+    let b = &b[strip(b)..];
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
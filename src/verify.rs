@@ -0,0 +1,94 @@
+use crate::checksum::sha256_file;
+use crate::facts::Facts;
+use std::path::{Path, PathBuf};
+
+/// State of one forge-managed executable compared with its recorded hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Integrity {
+    Intact,
+    /// Contents changed since install
+    Modified,
+    /// Now holds another tool's binary, or a link to something else
+    Replaced {
+        by: String,
+    },
+    Deleted,
+}
+
+impl Integrity {
+    pub fn is_drift(&self) -> bool {
+        !matches!(self, Integrity::Intact)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecutableCheck {
+    pub tool: String,
+    pub executable: String,
+    pub path: PathBuf,
+    pub integrity: Integrity,
+}
+
+/// Rehash every executable forge recorded under `bin_dir`
+///
+/// Executables living elsewhere belong to their package manager, which
+/// upgrades them on its own schedule, so they are not checked.
+pub fn check_executables(facts: &Facts, bin_dir: &Path) -> Vec<ExecutableCheck> {
+    let mut checks = Vec::new();
+
+    for (tool, fact) in &facts.tools {
+        let Some(provenance) = &fact.provenance else {
+            continue;
+        };
+
+        for (executable, record) in &provenance.executables {
+            if !record.path.starts_with(bin_dir) {
+                continue;
+            }
+
+            checks.push(ExecutableCheck {
+                tool: tool.clone(),
+                executable: executable.clone(),
+                path: record.path.clone(),
+                integrity: check_one(facts, tool, &record.path, &record.sha256),
+            });
+        }
+    }
+
+    checks.sort_by(|a, b| (&a.tool, &a.executable).cmp(&(&b.tool, &b.executable)));
+    checks
+}
+
+fn check_one(facts: &Facts, tool: &str, path: &Path, expected: &str) -> Integrity {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return Integrity::Deleted;
+    };
+
+    if metadata.file_type().is_symlink() {
+        let target = std::fs::read_link(path)
+            .map(|t| t.display().to_string())
+            .unwrap_or_else(|_| "a symlink".to_string());
+        return Integrity::Replaced { by: target };
+    }
+
+    let Ok(actual) = sha256_file(path) else {
+        return Integrity::Deleted;
+    };
+    if actual == expected {
+        return Integrity::Intact;
+    }
+
+    // Another forge tool may have written the same file name
+    let owner = facts.tools.iter().find(|(other, fact)| {
+        other.as_str() != tool
+            && fact
+                .provenance
+                .as_ref()
+                .is_some_and(|p| p.executables.values().any(|r| r.sha256 == actual))
+    });
+
+    match owner {
+        Some((other, _)) => Integrity::Replaced { by: other.clone() },
+        None => Integrity::Modified,
+    }
+}
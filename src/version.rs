@@ -1,16 +1,17 @@
+use crate::command::CommandRunner;
 use crate::knowledge::VersionCheck;
 use anyhow::Result;
 use serde_json::Value;
-use std::process::Command;
 
 fn normalize_version(version: &str) -> String {
     version.trim().trim_start_matches('v').to_string()
 }
 
-pub async fn check_latest_version(
+pub fn check_latest_version(
     _installer_name: &str,
     package: &str,
     version_check: Option<&VersionCheck>,
+    runner: &dyn CommandRunner,
 ) -> Result<Option<String>> {
     // If no version check config, can't check
     let check = match version_check {
@@ -30,7 +31,7 @@ pub async fn check_latest_version(
                     })
                     .collect();
 
-                let output = Command::new(&command[0]).args(&command[1..]).output()?;
+                let output = runner.run(&command[0], &command[1..])?;
 
                 if output.status.success() {
                     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -57,7 +58,7 @@ pub async fn check_latest_version(
                     .replace("{package}", package)
                     .replace("{repo}", package);
 
-                let output = Command::new("curl").args(["-s", &url]).output()?;
+                let output = runner.run("curl", &["-s".to_string(), url.clone()])?;
 
                 if output.status.success() {
                     let response = String::from_utf8_lossy(&output.stdout);
@@ -65,23 +66,11 @@ pub async fn check_latest_version(
                     // For crates.io API, extract the exact version
                     if url.contains("crates.io")
                         && check.path.as_deref() == Some("crate.max_version")
+                        && let Ok(json) = serde_json::from_str::<Value>(&response)
+                        && let Some(version) =
+                            json.pointer("/crate/max_version").and_then(|v| v.as_str())
                     {
-                        if let Ok(json) = serde_json::from_str::<Value>(&response) {
-                            if let Some(version) =
-                                json.pointer("/crate/max_version").and_then(|v| v.as_str())
-                            {
-                                return Ok(Some(normalize_version(version)));
-                            }
-                        }
-                    }
-
-                    // For GitHub API, extract tag name
-                    if url.contains("api.github.com") && check.path.as_deref() == Some("tag_name") {
-                        if let Ok(json) = serde_json::from_str::<Value>(&response) {
-                            if let Some(version) = json.get("tag_name").and_then(|v| v.as_str()) {
-                                return Ok(Some(normalize_version(version)));
-                            }
-                        }
+                        return Ok(Some(normalize_version(version)));
                     }
                 }
             }
@@ -111,17 +100,13 @@ fn extract_brew_version(json_output: &str) -> Option<String> {
     // Parse brew's JSON output to get installed version
     if let Ok(json) = serde_json::from_str::<Value>(json_output) {
         // brew info returns { "formulae": [...], "casks": [...] }
-        if let Some(formulae) = json.get("formulae").and_then(|f| f.as_array()) {
-            if let Some(formula) = formulae.first() {
-                if let Some(installed) = formula.get("installed").and_then(|i| i.as_array()) {
-                    if let Some(version_info) = installed.first() {
-                        if let Some(version) = version_info.get("version").and_then(|v| v.as_str())
-                        {
-                            return Some(version.to_string());
-                        }
-                    }
-                }
-            }
+        if let Some(formulae) = json.get("formulae").and_then(|f| f.as_array())
+            && let Some(formula) = formulae.first()
+            && let Some(installed) = formula.get("installed").and_then(|i| i.as_array())
+            && let Some(version_info) = installed.first()
+            && let Some(version) = version_info.get("version").and_then(|v| v.as_str())
+        {
+            return Some(version.to_string());
         }
     }
     None
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use forge::archive::{self, ArchiveKind};
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+const ELF: &[u8] = b"\x7fELF\x02\x01\x01\x00fake binary";
+
+fn tar_bytes(entries: &[(&str, &[u8], u32)]) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, data, mode) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(*mode);
+        header.set_cksum();
+        builder.append_data(&mut header, path, *data)?;
+    }
+    Ok(builder.into_inner()?)
+}
+
+fn write_tar_gz(path: &Path, entries: &[(&str, &[u8], u32)]) -> Result<()> {
+    let mut encoder =
+        flate2::write::GzEncoder::new(std::fs::File::create(path)?, flate2::Compression::default());
+    encoder.write_all(&tar_bytes(entries)?)?;
+    encoder.finish()?;
+    Ok(())
+}
+
+fn write_zip(path: &Path, entries: &[(&str, &[u8], u32)]) -> Result<()> {
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(path)?);
+    for (name, data, mode) in entries {
+        let options = zip::write::SimpleFileOptions::default().unix_permissions(*mode);
+        writer.start_file(*name, options)?;
+        writer.write_all(data)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+#[test]
+fn test_archive_kind_from_name() {
+    assert_eq!(
+        ArchiveKind::from_name("rg-x86_64-unknown-linux-musl.tar.gz"),
+        Some(ArchiveKind::TarGz)
+    );
+    assert_eq!(ArchiveKind::from_name("tool.tgz"), Some(ArchiveKind::TarGz));
+    assert_eq!(
+        ArchiveKind::from_name("tool.tar.xz"),
+        Some(ArchiveKind::TarXz)
+    );
+    assert_eq!(
+        ArchiveKind::from_name("tool.tar.bz2"),
+        Some(ArchiveKind::TarBz2)
+    );
+    assert_eq!(ArchiveKind::from_name("tool.zip"), Some(ArchiveKind::Zip));
+    assert_eq!(ArchiveKind::from_name("tool-linux-amd64"), None);
+}
+
+#[test]
+fn test_executables_found_by_content_not_name() -> Result<()> {
+    let work = tempfile::tempdir()?;
+    let archive_path = work.path().join("tool.tar.gz");
+    write_tar_gz(
+        &archive_path,
+        &[
+            // Native binary without the exec bit
+            ("tool-1.0/tool", ELF, 0o644),
+            // Script with the exec bit
+            ("tool-1.0/bin/helper.sh", b"#!/bin/sh\necho hi\n", 0o755),
+            // Executable-looking names and modes that are not executables
+            ("tool-1.0/README", b"read me", 0o755),
+            (
+                "tool-1.0/complete/tool.bash",
+                b"complete -F _tool tool",
+                0o644,
+            ),
+            ("tool-1.0/unmarked.sh", b"#!/bin/sh\n", 0o644),
+        ],
+    )?;
+
+    let dest = work.path().join("out");
+    archive::extract(&archive_path, ArchiveKind::TarGz, &dest)?;
+
+    assert_eq!(
+        archive::find_executables(&dest)?,
+        vec![
+            PathBuf::from("tool-1.0/bin/helper.sh"),
+            PathBuf::from("tool-1.0/tool"),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_compressed_tar_formats() -> Result<()> {
+    let work = tempfile::tempdir()?;
+    let tar = tar_bytes(&[("tool", ELF, 0o755)])?;
+
+    let xz_path = work.path().join("tool.tar.xz");
+    let mut xz = xz2::write::XzEncoder::new(std::fs::File::create(&xz_path)?, 6);
+    xz.write_all(&tar)?;
+    xz.finish()?;
+
+    let bz_path = work.path().join("tool.tar.bz2");
+    let mut bz = bzip2::write::BzEncoder::new(
+        std::fs::File::create(&bz_path)?,
+        bzip2::Compression::default(),
+    );
+    bz.write_all(&tar)?;
+    bz.finish()?;
+
+    for (path, kind) in [
+        (xz_path, ArchiveKind::TarXz),
+        (bz_path, ArchiveKind::TarBz2),
+    ] {
+        let dest = work.path().join(format!("{:?}", kind));
+        archive::extract(&path, kind, &dest)?;
+        assert_eq!(std::fs::read(dest.join("tool"))?, ELF);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_zip_preserves_mode() -> Result<()> {
+    let work = tempfile::tempdir()?;
+    let archive_path = work.path().join("tool.zip");
+    write_zip(
+        &archive_path,
+        &[
+            ("tool/run", b"#!/bin/sh\n", 0o755),
+            ("tool/notes.txt", b"notes", 0o644),
+        ],
+    )?;
+
+    let dest = work.path().join("out");
+    archive::extract(&archive_path, ArchiveKind::Zip, &dest)?;
+
+    let mode = std::fs::metadata(dest.join("tool/run"))?
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o755);
+    assert_eq!(
+        archive::find_executables(&dest)?,
+        vec![PathBuf::from("tool/run")]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_tar_path_traversal_is_rejected() -> Result<()> {
+    let work = tempfile::tempdir()?;
+
+    // tar::Builder refuses `..`, so write the entry name into the header directly
+    let mut header = tar::Header::new_old();
+    let name = b"../escaped";
+    header.as_old_mut().name[..name.len()].copy_from_slice(name);
+    header.set_size(3);
+    header.set_mode(0o755);
+    header.set_cksum();
+
+    let mut builder = tar::Builder::new(Vec::new());
+    builder.append(&header, &b"bad"[..])?;
+    let archive_path = work.path().join("evil.tar.gz");
+    let mut encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&archive_path)?,
+        flate2::Compression::default(),
+    );
+    encoder.write_all(&builder.into_inner()?)?;
+    encoder.finish()?;
+
+    let dest = work.path().join("out");
+    let err = archive::extract(&archive_path, ArchiveKind::TarGz, &dest).unwrap_err();
+
+    assert!(err.to_string().contains("escapes the archive"));
+    assert!(!work.path().join("escaped").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_zip_path_traversal_is_rejected() -> Result<()> {
+    let work = tempfile::tempdir()?;
+    let archive_path = work.path().join("evil.zip");
+    write_zip(&archive_path, &[("../escaped", b"bad", 0o755)])?;
+
+    let dest = work.path().join("out");
+    let err = archive::extract(&archive_path, ArchiveKind::Zip, &dest).unwrap_err();
+
+    assert!(err.to_string().contains("escapes the archive"));
+    assert!(!work.path().join("escaped").exists());
+
+    Ok(())
+}
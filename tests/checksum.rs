@@ -0,0 +1,86 @@
+use anyhow::Result;
+use forge::checksum::{find_checksum_asset, parse_checksum, sha256_file, verify_sha256};
+
+const DIGEST_A: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+const DIGEST_B: &str = "486ea46224d1bb4fb680f34f7c9ad96a8f24ec88be73ea8e5a6c65260e9cb8a7";
+
+#[test]
+fn test_parse_sha256sums_listing() {
+    let listing = format!(
+        "{}  tool-linux-x86_64.tar.gz\n{} *./dist/tool-darwin-arm64.tar.gz\n",
+        DIGEST_A, DIGEST_B
+    );
+
+    assert_eq!(
+        parse_checksum(&listing, "tool-linux-x86_64.tar.gz").as_deref(),
+        Some(DIGEST_A)
+    );
+    assert_eq!(
+        parse_checksum(&listing, "tool-darwin-arm64.tar.gz").as_deref(),
+        Some(DIGEST_B)
+    );
+    assert_eq!(parse_checksum(&listing, "tool-windows.zip"), None);
+}
+
+#[test]
+fn test_parse_single_digest_file() {
+    assert_eq!(
+        parse_checksum(&format!("{}\n", DIGEST_A.to_uppercase()), "anything").as_deref(),
+        Some(DIGEST_A)
+    );
+    assert_eq!(parse_checksum("not a digest", "anything"), None);
+}
+
+#[test]
+fn test_find_checksum_asset_prefers_dedicated_file() {
+    let names = [
+        "tool.tar.gz",
+        "checksums.txt",
+        "checksums.txt.sig",
+        "tool.tar.gz.sha256",
+    ];
+    assert_eq!(
+        find_checksum_asset(&names, "tool.tar.gz"),
+        Some("tool.tar.gz.sha256")
+    );
+    assert_eq!(
+        find_checksum_asset(&names, "tool.zip"),
+        Some("checksums.txt")
+    );
+    assert_eq!(find_checksum_asset(&["tool.tar.gz"], "tool.tar.gz"), None);
+}
+
+#[test]
+fn test_find_checksum_asset_skips_other_assets_digests() {
+    let names = [
+        "foo.tar.gz",
+        "foo.tar.gz.sha256sum",
+        "foo.tar.gz.sha512",
+        "tool.tar.gz",
+    ];
+    assert_eq!(find_checksum_asset(&names, "tool.tar.gz"), None);
+
+    let names = [
+        "foo.tar.gz.sha256sum",
+        "checksums.sha512",
+        "tool.tar.gz",
+        "SHA256SUMS",
+    ];
+    assert_eq!(
+        find_checksum_asset(&names, "tool.tar.gz"),
+        Some("SHA256SUMS")
+    );
+}
+
+#[test]
+fn test_verify_sha256() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("hello");
+    std::fs::write(&path, "hello")?;
+
+    assert_eq!(sha256_file(&path)?, DIGEST_A);
+    verify_sha256(&path, DIGEST_A, "test")?;
+    assert!(verify_sha256(&path, DIGEST_B, "test").is_err());
+
+    Ok(())
+}
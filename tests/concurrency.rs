@@ -0,0 +1,215 @@
+use anyhow::Result;
+use forge::command::CommandRunner;
+use forge::command::mock::MockCommandRunner;
+use forge::facts::Facts;
+use forge::forge::Forge;
+use forge::output;
+use forge::paths::Paths;
+use forge::project::PROJECT_FILE;
+use std::collections::BTreeMap;
+use std::process::Output;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+/// Holds each gated command until a second one of the same program is
+/// running too, or a short timeout passes, and records the most that ran
+/// at once
+#[derive(Default)]
+struct GateRunner {
+    inner: MockCommandRunner,
+    /// Program -> (running now, most running at once)
+    running: Mutex<BTreeMap<String, (usize, usize)>>,
+    changed: Condvar,
+}
+
+impl GateRunner {
+    fn most_at_once(&self, program: &str) -> usize {
+        self.running.lock().unwrap().get(program).map_or(0, |c| c.1)
+    }
+}
+
+impl CommandRunner for GateRunner {
+    fn run(&self, program: &str, args: &[String]) -> Result<Output> {
+        let gated = match program {
+            "curl" | "sudo" => true,
+            "cargo" => args.first().is_some_and(|a| a == "install"),
+            _ => false,
+        };
+        if !gated {
+            return self.inner.run(program, args);
+        }
+
+        {
+            let mut running = self.running.lock().unwrap();
+            let counts = running.entry(program.to_string()).or_default();
+            counts.0 += 1;
+            counts.1 = counts.1.max(counts.0);
+            self.changed.notify_all();
+            let _ = self
+                .changed
+                .wait_timeout_while(running, Duration::from_millis(500), |running| {
+                    running[program].0 < 2
+                })
+                .unwrap();
+        }
+
+        let output = self.inner.run(program, args);
+        self.running.lock().unwrap().get_mut(program).unwrap().0 -= 1;
+        output
+    }
+}
+
+#[tokio::test]
+async fn test_update_checks_run_concurrently() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let runner = Arc::new(GateRunner::default());
+    let mock = &runner.inner;
+
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.1.0`",
+        true,
+    );
+    mock.expect(
+        "cargo",
+        &["install", "fd-find", "--locked"],
+        "Installed package `fd-find v10.2.0`",
+        true,
+    );
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/ripgrep"],
+        r#"{"crate": {"max_version": "14.1.0"}}"#,
+        true,
+    );
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/fd-find"],
+        r#"{"crate": {"max_version": "10.2.0"}}"#,
+        true,
+    );
+
+    let forge = Forge::with_environment(runner.clone(), paths.clone()).await?;
+    forge.install("ripgrep", None).await?;
+    forge.install("fd", None).await?;
+
+    forge.update(None, true).await?;
+    assert_eq!(runner.most_at_once("curl"), 2);
+
+    // With a single job the checks go one at a time
+    let runner = Arc::new(GateRunner::default());
+    for crate_name in ["ripgrep", "fd-find"] {
+        runner.inner.expect(
+            "curl",
+            &[
+                "-s",
+                &format!("https://crates.io/api/v1/crates/{}", crate_name),
+            ],
+            r#"{"crate": {"max_version": "1.0.0"}}"#,
+            true,
+        );
+    }
+    let forge = Forge::with_environment(runner.clone(), paths.clone())
+        .await?
+        .jobs(1);
+    forge.update(None, true).await?;
+    assert_eq!(runner.most_at_once("curl"), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_installs_in_parallel_except_locking_installers() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let project = tempfile::tempdir()?;
+    let manifest_path = project.path().join(PROJECT_FILE);
+    std::fs::write(
+        &manifest_path,
+        r#"
+tools = ["bat", "git-flow", "ripgrep", "fd"]
+
+[installers]
+bat = "apt"
+git-flow = "apt"
+ripgrep = "cargo"
+fd = "cargo"
+"#,
+    )?;
+
+    let runner = Arc::new(GateRunner::default());
+    let mock = &runner.inner;
+    mock.expect("apt", &["--version"], "apt 2.7.14 (amd64)", true);
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "sudo",
+        &["apt", "install", "-y", "bat"],
+        "Setting up bat (0.24.0-1) ...",
+        true,
+    );
+    mock.expect(
+        "sudo",
+        &["apt", "install", "-y", "git-flow"],
+        "Setting up git-flow (1.12.3-3) ...",
+        true,
+    );
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.1.0`",
+        true,
+    );
+    mock.expect(
+        "cargo",
+        &["install", "fd-find", "--locked"],
+        "Installed package `fd-find v10.2.0`",
+        true,
+    );
+
+    let forge = Forge::with_environment(runner.clone(), paths.clone()).await?;
+    forge.apply(manifest_path.to_str(), false, false).await?;
+
+    assert_eq!(runner.most_at_once("cargo"), 2);
+    assert_eq!(runner.most_at_once("sudo"), 1);
+
+    // Every install made it into the facts file
+    let facts = Facts::load_from(&paths).await?;
+    let mut installed: Vec<&str> = facts.tools.keys().map(|k| k.as_str()).collect();
+    installed.sort();
+    assert_eq!(installed, vec!["bat", "fd", "git-flow", "ripgrep"]);
+    assert_eq!(facts.tools["git-flow"].version.as_deref(), Some("1.12.3-3"));
+
+    Ok(())
+}
+
+#[test]
+fn test_captured_output_stays_with_its_job() {
+    let jobs: Vec<_> = (0..2)
+        .map(|job| {
+            std::thread::spawn(move || {
+                output::capture(|| {
+                    for line in 0..3 {
+                        output::line(format_args!("job {} line {}", job, line));
+                        std::thread::yield_now();
+                    }
+                    job
+                })
+            })
+        })
+        .collect();
+
+    for handle in jobs {
+        let (job, text) = handle.join().unwrap();
+        assert_eq!(
+            text,
+            format!("job {job} line 0\njob {job} line 1\njob {job} line 2\n")
+        );
+    }
+
+    // Capturing ends with the job
+    let ((), text) = output::capture(|| ());
+    assert!(text.is_empty());
+}
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use chrono::Utc;
+use forge::command::mock::MockCommandRunner;
+use forge::doctor::{Finding, Severity};
+use forge::facts::{ExecutableRecord, Facts, Provenance, ToolFact};
+use forge::forge::Forge;
+use forge::paths::Paths;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::sync::Arc;
+
+fn write_executable(path: &Path, contents: &str) -> Result<()> {
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, contents)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+fn fact(installer: &str, executables: Option<Vec<String>>) -> ToolFact {
+    ToolFact {
+        installed_at: Utc::now(),
+        installer: installer.to_string(),
+        version: Some("1.0.0".to_string()),
+        executables,
+        provenance: None,
+        pin: None,
+    }
+}
+
+fn find<'a>(findings: &'a [Finding], category: &str, needle: &str) -> Option<&'a Finding> {
+    findings
+        .iter()
+        .find(|f| f.category == category && f.message.contains(needle))
+}
+
+#[tokio::test]
+async fn test_doctor_reports_problems_with_fixes() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let shadow_dir = home.path().join("shadow");
+    let base = Paths::new(home.path());
+    let search_path = std::env::join_paths([shadow_dir.as_path(), base.bin_dir()])?;
+    let paths = base.with_search_path(search_path);
+
+    // uv is installed by forge, but another uv comes first on PATH
+    let uv = paths.bin_dir().join("uv");
+    write_executable(&uv, "#!/bin/sh\necho forge uv\n")?;
+    write_executable(&shadow_dir.join("uv"), "#!/bin/sh\necho other uv\n")?;
+
+    let mut facts = Facts::default();
+    let mut uv_fact = fact("github", Some(vec!["uv".to_string()]));
+    let mut provenance = Provenance::default();
+    provenance.executables.insert(
+        "uv".to_string(),
+        ExecutableRecord {
+            path: uv.clone(),
+            sha256: forge::checksum::sha256_file(&uv)?,
+        },
+    );
+    // One of its two executables was deleted
+    provenance.executables.insert(
+        "uvx".to_string(),
+        ExecutableRecord {
+            path: paths.bin_dir().join("uvx"),
+            sha256: String::new(),
+        },
+    );
+    uv_fact.provenance = Some(provenance);
+    facts.tools.insert("uv".to_string(), uv_fact);
+    // Recorded, but its binary is gone
+    facts.tools.insert(
+        "ripgrep".to_string(),
+        fact("github", Some(vec!["rg".to_string()])),
+    );
+    // Recorded, but no longer known
+    facts
+        .tools
+        .insert("retired".to_string(), fact("cargo", None));
+    facts.save_to(&paths).await?;
+
+    std::fs::write(paths.local_knowledge(), "[tools.broken\n")?;
+
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect("apt", &["--version"], "", false);
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    let findings = forge.diagnose().await?;
+
+    let on_path = find(&findings, "PATH", "is on PATH").expect("PATH finding");
+    assert_eq!(on_path.severity, Severity::Ok);
+
+    let cargo = find(&findings, "Installers", "cargo").expect("cargo finding");
+    assert_eq!(cargo.severity, Severity::Ok);
+    let apt = find(&findings, "Installers", "apt").expect("apt finding");
+    assert_eq!(apt.severity, Severity::Warning);
+
+    let gh = find(&findings, "GitHub", "gh is not installed").expect("gh finding");
+    assert_eq!(gh.fix.as_deref(), Some("forge install gh"));
+
+    let knowledge = find(&findings, "Knowledge", "does not parse").expect("knowledge finding");
+    assert_eq!(knowledge.severity, Severity::Error);
+
+    let stale = find(&findings, "Facts", "rg no longer exists").expect("stale fact");
+    assert_eq!(
+        stale.fix.as_deref(),
+        Some("forge uninstall ripgrep && forge install ripgrep")
+    );
+    let partial = find(&findings, "Facts", "uv is recorded").expect("partly missing uv");
+    assert!(partial.message.ends_with("uvx no longer exists"));
+    let retired = find(&findings, "Facts", "retired").expect("unknown tool");
+    assert_eq!(retired.fix.as_deref(), Some("forge uninstall retired"));
+
+    let shadowed = find(&findings, "Shadowing", "uv from uv").expect("shadowed uv");
+    assert!(shadowed.message.contains(&shadow_dir.display().to_string()));
+    assert!(shadowed.fix.is_some());
+
+    // Every problem comes with a suggested fix
+    assert!(
+        findings
+            .iter()
+            .filter(|f| f.is_problem())
+            .all(|f| f.fix.is_some())
+    );
+
+    forge.doctor().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_doctor_flags_bin_dir_missing_from_path() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path()).with_search_path("/usr/bin:/bin");
+
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("gh", &["--version"], "gh version 2.74.0", true);
+    mock.expect("gh", &["auth", "status"], "Logged in", true);
+
+    let forge = Forge::with_environment(mock, paths.clone()).await?;
+    let findings = forge.diagnose().await?;
+
+    let path = find(&findings, "PATH", "is not on PATH").expect("PATH finding");
+    assert_eq!(path.severity, Severity::Error);
+    assert!(
+        path.fix
+            .as_deref()
+            .is_some_and(|fix| fix.contains(&paths.bin_dir().display().to_string()))
+    );
+
+    let gh = find(&findings, "GitHub", "authenticated").expect("gh finding");
+    assert_eq!(gh.severity, Severity::Ok);
+
+    Ok(())
+}
@@ -0,0 +1,855 @@
+mod support;
+
+use anyhow::Result;
+use chrono::Utc;
+use forge::command::mock::MockCommandRunner;
+use forge::facts::{Facts, Pin, ToolFact};
+use forge::forge::Forge;
+use forge::github::GitHubClient;
+use forge::paths::Paths;
+use forge::report::{CandidateStatus, PlannedAction};
+use forge::updates::UpdateStatus;
+use std::io::Write;
+use std::sync::Arc;
+use support::MockServer;
+
+#[tokio::test]
+async fn test_install_list_update_uninstall() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.0.3`",
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+
+    // Install
+    forge.install("ripgrep", None).await?;
+    let facts = Facts::load_from(&paths).await?;
+    let fact = facts
+        .tools
+        .get("ripgrep")
+        .expect("ripgrep should be recorded");
+    assert_eq!(fact.installer, "cargo");
+    assert_eq!(fact.version.as_deref(), Some("14.0.3"));
+    assert_eq!(
+        fact.provenance.as_ref().and_then(|p| p.command.clone()),
+        Some(
+            ["cargo", "install", "ripgrep", "--locked"]
+                .map(String::from)
+                .to_vec()
+        )
+    );
+
+    // List
+    forge.list(false).await?;
+    forge.list(true).await?;
+
+    // Update - crates.io reports a newer release
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/ripgrep"],
+        r#"{"crate": {"max_version": "14.1.0"}}"#,
+        true,
+    );
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked", "--force"],
+        "Replaced package `ripgrep v14.0.3` with `ripgrep v14.1.0` (executable `rg`)",
+        true,
+    );
+
+    forge.update(Some("ripgrep"), true).await?;
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(
+        facts
+            .tools
+            .get("ripgrep")
+            .and_then(|f| f.version.as_deref()),
+        Some("14.1.0")
+    );
+
+    // Uninstall
+    mock.expect("cargo", &["uninstall", "ripgrep"], "", true);
+    forge.uninstall("ripgrep").await?;
+    let facts = Facts::load_from(&paths).await?;
+    assert!(facts.tools.is_empty());
+
+    // The update ran in place rather than uninstalling first
+    let calls = mock.calls();
+    assert!(calls.contains(&"cargo install ripgrep --locked --force".to_string()));
+    assert_eq!(
+        calls
+            .iter()
+            .filter(|c| *c == "cargo uninstall ripgrep")
+            .count(),
+        1
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_specific_version() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked", "--version", "14.0.3"],
+        "Installed package `ripgrep v14.0.3`",
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    forge.install("ripgrep@14.0.3", None).await?;
+
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(
+        facts
+            .tools
+            .get("ripgrep")
+            .and_then(|f| f.version.as_deref()),
+        Some("14.0.3")
+    );
+
+    // The same version again is a no-op; another one replaces it
+    forge.install("ripgrep@14.0.3", None).await?;
+    mock.expect("cargo", &["uninstall", "ripgrep"], "", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked", "--version", "13.0.0"],
+        "Installed package `ripgrep v13.0.0`",
+        true,
+    );
+    forge.install("ripgrep@13.0.0", None).await?;
+
+    let facts = Facts::load_from(&paths).await?;
+    let fact = facts
+        .tools
+        .get("ripgrep")
+        .expect("ripgrep should be recorded");
+    assert_eq!(fact.installer, "cargo");
+    assert_eq!(fact.version.as_deref(), Some("13.0.0"));
+
+    // Scripts have no way to pick a version
+    let err = forge.install("k3s@1.30.0", None).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("cannot install a specific version of k3s")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_skips_pinned_tools() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.0.3`",
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    forge.install("ripgrep", None).await?;
+
+    // A plain pin never even looks for a newer release
+    forge.pin("ripgrep", None).await?;
+    forge.update(None, true).await?;
+    forge.update(Some("ripgrep"), true).await?;
+    assert!(!mock.calls().iter().any(|c| c.starts_with("curl")));
+
+    // A range pin only lets updates through inside the range
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/ripgrep"],
+        r#"{"crate": {"max_version": "15.0.0"}}"#,
+        true,
+    );
+    forge.pin("ripgrep", Some("14")).await?;
+    forge.update(None, true).await?;
+    assert!(!mock.calls().iter().any(|c| c.ends_with("--force")));
+
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/ripgrep"],
+        r#"{"crate": {"max_version": "14.1.0"}}"#,
+        true,
+    );
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked", "--force"],
+        "Replaced package `ripgrep v14.0.3` with `ripgrep v14.1.0` (executable `rg`)",
+        true,
+    );
+    forge.update(None, true).await?;
+
+    let facts = Facts::load_from(&paths).await?;
+    let fact = facts
+        .tools
+        .get("ripgrep")
+        .expect("ripgrep should be recorded");
+    assert_eq!(fact.version.as_deref(), Some("14.1.0"));
+    assert_eq!(
+        fact.pin.as_ref().and_then(|p| p.range.as_deref()),
+        Some("14")
+    );
+
+    forge.unpin("ripgrep").await?;
+    let facts = Facts::load_from(&paths).await?;
+    assert!(facts.tools.get("ripgrep").is_some_and(|f| f.pin.is_none()));
+
+    // Ranges match whole version components, not string prefixes
+    let pin = Pin {
+        range: Some("1.2".to_string()),
+    };
+    assert!(pin.allows("1.2"));
+    assert!(pin.allows("1.2.9"));
+    assert!(pin.allows("v1.2.0-rc.1"));
+    assert!(!pin.allows("1.20.0"));
+    assert!(!pin.allows("1.3.0"));
+    let pin = Pin {
+        range: Some("1.2.*".to_string()),
+    };
+    assert!(pin.allows("1.2.3"));
+    assert!(!pin.allows("1.21"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uninstall_removes_recorded_executables() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    std::fs::create_dir_all(paths.bin_dir())?;
+    std::fs::write(paths.bin_dir().join("uv"), "#!/bin/sh\n")?;
+
+    let mut facts = Facts::default();
+    facts.tools.insert(
+        "uv".to_string(),
+        ToolFact {
+            installed_at: Utc::now(),
+            installer: "github".to_string(),
+            version: Some("0.7.0".to_string()),
+            executables: Some(vec!["uv".to_string()]),
+            provenance: None,
+            pin: None,
+        },
+    );
+    facts.save_to(&paths).await?;
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    forge.uninstall("uv").await?;
+
+    assert!(!paths.bin_dir().join("uv").exists());
+    assert!(Facts::load_from(&paths).await?.tools.is_empty());
+    // The github installer has no uninstall command to run
+    assert!(mock.calls().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dry_run_leaves_state_untouched() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .dry_run(true);
+    forge.install("ripgrep", None).await?;
+
+    assert!(!paths.facts_file().exists());
+    assert_eq!(mock.calls(), vec!["cargo --version".to_string()]);
+
+    // A script install has no installer check, so nothing runs at all
+    let mock = Arc::new(MockCommandRunner::new());
+    let forge = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .dry_run(true);
+    forge.install("k3s", None).await?;
+
+    assert!(!paths.facts_file().exists());
+    assert!(mock.calls().is_empty(), "{:?}", mock.calls());
+
+    Ok(())
+}
+
+#[test]
+fn test_dry_run_is_rejected_by_commands_that_ignore_it() {
+    let forge = env!("CARGO_BIN_EXE_forge");
+    for args in [["fmt", "--dry-run"], ["sync", "--dry-run"]] {
+        let output = std::process::Command::new(forge)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(!output.status.success(), "{:?} should be rejected", args);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("--dry-run"));
+    }
+}
+
+#[test]
+fn test_jobs_is_rejected_by_commands_that_run_one_thing() {
+    let forge = env!("CARGO_BIN_EXE_forge");
+    for args in [["fmt", "-j", "4"], ["why", "-j", "2"]] {
+        let output = std::process::Command::new(forge)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(!output.status.success(), "{:?} should be rejected", args);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("-j"));
+    }
+}
+
+#[tokio::test]
+async fn test_github_install_records_provenance() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    let mut builder = tar::Builder::new(Vec::new());
+    for name in ["uv", "uvx"] {
+        let data = format!("\x7fELF {}", name);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, format!("uv-x86_64/{}", name), data.as_bytes())?;
+    }
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&builder.into_inner()?)?;
+
+    let asset = "uv-x86_64-unknown-linux-gnu.tar.gz";
+    let server = MockServer::start();
+    server.route(
+        "/repos/astral-sh/uv/releases/latest",
+        200,
+        format!(
+            r#"{{"tag_name": "0.7.2", "assets": [{{"name": "{asset}", "browser_download_url": "{url}/download/{asset}"}}]}}"#,
+            asset = asset,
+            url = server.url
+        ),
+    );
+    server.route(&format!("/download/{}", asset), 200, encoder.finish()?);
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .github_client(GitHubClient::new(&server.url, None));
+    forge.install("uv", Some("github")).await?;
+
+    let facts = Facts::load_from(&paths).await?;
+    let provenance = facts.tools["uv"]
+        .provenance
+        .clone()
+        .expect("provenance should be recorded");
+
+    assert_eq!(provenance.tag.as_deref(), Some("0.7.2"));
+    assert_eq!(provenance.downloads.len(), 1);
+    assert_eq!(provenance.downloads[0].asset, asset);
+    assert_eq!(
+        provenance.downloads[0].url,
+        format!("{}/download/{}", server.url, asset)
+    );
+
+    let uv = &provenance.executables["uv"];
+    assert_eq!(uv.path, paths.bin_dir().join("uv"));
+    assert_eq!(uv.sha256, forge::checksum::sha256_file(&uv.path)?);
+    assert!(provenance.executables.contains_key("uvx"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_reinstall_restores_previous_binaries() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    let asset = "uv-x86_64-unknown-linux-gnu";
+    let release = |tag: &str, url: &str| {
+        format!(
+            r#"{{"tag_name": "{tag}", "assets": [{{"name": "{asset}", "browser_download_url": "{url}/download/{tag}/{asset}"}}]}}"#
+        )
+    };
+    let server = MockServer::start();
+    server.route(
+        "/repos/astral-sh/uv/releases/latest",
+        200,
+        release("0.7.2", &server.url),
+    );
+    server.route(
+        &format!("/download/0.7.2/{}", asset),
+        200,
+        "\x7fELF uv 0.7.2",
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .github_client(GitHubClient::new(&server.url, None));
+    forge.install("uv", Some("github")).await?;
+
+    // The new release's download is missing, so the reinstall fails
+    server.route(
+        "/repos/astral-sh/uv/releases/latest",
+        200,
+        release("0.8.0", &server.url),
+    );
+    assert!(forge.update(Some("uv"), true).await.is_err());
+
+    assert_eq!(
+        std::fs::read_to_string(paths.bin_dir().join("uv"))?,
+        "\x7fELF uv 0.7.2"
+    );
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["uv"].version.as_deref(), Some("0.7.2"));
+    assert_eq!(std::fs::read_dir(paths.backup_dir())?.count(), 0);
+
+    // Switching to a release whose download fails changes nothing either
+    server.route(
+        "/repos/astral-sh/uv/releases/tags/0.8.0",
+        200,
+        release("0.8.0", &server.url),
+    );
+    assert!(forge.install("uv@0.8.0", None).await.is_err());
+
+    assert_eq!(
+        std::fs::read_to_string(paths.bin_dir().join("uv"))?,
+        "\x7fELF uv 0.7.2"
+    );
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["uv"].version.as_deref(), Some("0.7.2"));
+    let leftovers: Vec<_> = std::fs::read_dir(paths.bin_dir())?
+        .map(|entry| entry.map(|e| e.file_name()))
+        .collect::<std::io::Result<_>>()?;
+    assert_eq!(leftovers, ["uv"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_several_tools() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+
+    // Unknown names stop everything before anything runs. Tools are named
+    // as in the knowledge base, not by their executables.
+    let err = forge
+        .install_all(
+            &["ripgrep".to_string(), "rg".to_string(), "nope".to_string()],
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unknown tools: rg, nope");
+    assert!(mock.calls().is_empty());
+
+    let rust_script = "curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh -s -- -y";
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect("apt", &["--version"], "apt 2.7.14 (amd64)", true);
+    mock.expect("sh", &["-c", rust_script], "", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.1.0`",
+        true,
+    );
+    mock.expect("sudo", &["apt", "install", "-y", "bat"], "", false);
+
+    // A failure doesn't stop the others
+    let err = forge
+        .install_all(
+            &["ripgrep".to_string(), "bat".to_string(), "rust".to_string()],
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Failed to install 1 of 3 tools: bat");
+
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["ripgrep"].version.as_deref(), Some("14.1.0"));
+    assert_eq!(facts.tools["rust"].installer, "script");
+    assert!(!facts.tools.contains_key("bat"));
+
+    // rust provides cargo, so it goes before ripgrep
+    let calls = mock.calls();
+    let position = |call: &str| calls.iter().position(|c| c.starts_with(call)).unwrap();
+    assert!(position("sh -c") < position("cargo install ripgrep"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_outdated_changes_nothing() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.0.3`",
+        true,
+    );
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/ripgrep"],
+        r#"{"crate": {"max_version": "14.1.0"}}"#,
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    forge.install("ripgrep", None).await?;
+    let installed = mock.calls().len();
+
+    let checks = forge.outdated(None).await?;
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks[0].status, UpdateStatus::Available);
+    assert_eq!(checks[0].latest.as_deref(), Some("14.1.0"));
+
+    // Only the version lookup ran
+    assert_eq!(
+        &mock.calls()[installed..],
+        ["curl -s https://crates.io/api/v1/crates/ripgrep"]
+    );
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["ripgrep"].version.as_deref(), Some("14.0.3"));
+
+    assert!(forge.outdated(Some("fd")).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_info_looks_up_the_latest_version() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/ripgrep"],
+        r#"{"crate": {"max_version": "14.1.0"}}"#,
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths).await?;
+    forge.info("ripgrep").await?;
+
+    // Not installed, so the first installer this platform would use is asked
+    if cfg!(target_os = "linux") {
+        assert_eq!(
+            mock.calls(),
+            ["curl -s https://crates.io/api/v1/crates/ripgrep"]
+        );
+    }
+    assert!(forge.info("no-such-tool").await.is_err());
+
+    Ok(())
+}
+
+/// A home whose local knowledge declares `sd` for cargo and apt
+fn home_with_sd() -> Result<(tempfile::TempDir, Paths)> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    std::fs::create_dir_all(paths.forge_dir())?;
+    std::fs::write(
+        paths.local_knowledge(),
+        r#"
+[tools.sd]
+description = "Intuitive find and replace"
+provides = ["sd"]
+
+[tools.sd.installers.cargo]
+package = "sd"
+
+[tools.sd.installers.apt]
+package = "sd"
+"#,
+    )?;
+    Ok((home, paths))
+}
+
+#[tokio::test]
+async fn test_install_falls_back_to_the_next_usable_installer() -> Result<()> {
+    let (_home, paths) = home_with_sd()?;
+
+    // cargo comes first in the linux precedence, but its check fails
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("cargo", &["--version"], "", false);
+    mock.expect("apt", &["--version"], "apt 2.7.14 (amd64)", true);
+    mock.expect(
+        "sudo",
+        &["apt", "install", "-y", "sd"],
+        "Setting up sd (1.0.0-1) ...",
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    forge.install("sd", None).await?;
+
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["sd"].installer, "apt");
+    assert!(
+        !mock.calls().iter().any(|c| c.starts_with("cargo install")),
+        "the failed installer must not be used"
+    );
+
+    // With every candidate unusable the install fails and nothing runs
+    let (_home, paths) = home_with_sd()?;
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("cargo", &["--version"], "", false);
+    mock.expect("apt", &["--version"], "", false);
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    let err = forge.install("sd", None).await.unwrap_err();
+    assert!(err.to_string().contains("installer"), "{}", err);
+    assert_eq!(mock.calls(), ["cargo --version", "apt --version"]);
+    assert!(!paths.facts_file().exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_plan_explains_the_install_without_running_it() -> Result<()> {
+    let (_home, paths) = home_with_sd()?;
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("cargo", &["--version"], "", false);
+    mock.expect("apt", &["--version"], "apt 2.7.14 (amd64)", true);
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    let plan = forge.install_plan("sd@1.2.0").await?;
+
+    // Candidates follow the linux precedence, passing over the failed cargo
+    let candidates: Vec<(&str, CandidateStatus)> = plan
+        .candidates
+        .iter()
+        .map(|c| (c.installer.as_str(), c.status))
+        .collect();
+    assert_eq!(
+        candidates,
+        vec![
+            ("script", CandidateStatus::NotDeclared),
+            ("cargo", CandidateStatus::Unavailable),
+            ("github", CandidateStatus::NotDeclared),
+            ("apt", CandidateStatus::Selected),
+        ]
+    );
+    assert_eq!(plan.selected.as_deref(), Some("apt"));
+
+    // The requested version goes through the installer's version template
+    match plan.action {
+        Some(PlannedAction::Command { command }) => {
+            assert_eq!(command, ["sudo", "apt", "install", "-y", "sd=1.2.0"])
+        }
+        other => panic!("expected a command, got {:?}", other),
+    }
+
+    // Only the installer checks ran
+    assert_eq!(mock.calls(), ["cargo --version", "apt --version"]);
+    forge.plan("sd").await?;
+    assert!(mock.calls().iter().all(|c| c.ends_with(" --version")));
+    assert!(!paths.facts_file().exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_release_lookup_does_not_stop_other_checks() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+
+    let mut facts = Facts::default();
+    for (name, version) in [("uv", "0.7.0"), ("colima", "0.8.0")] {
+        facts.tools.insert(
+            name.to_string(),
+            ToolFact {
+                installed_at: Utc::now(),
+                installer: "github".to_string(),
+                version: Some(version.to_string()),
+                executables: None,
+                provenance: None,
+                pin: None,
+            },
+        );
+    }
+    facts.save_to(&paths).await?;
+
+    let server = MockServer::start();
+    server.route(
+        "/repos/astral-sh/uv/releases/latest",
+        200,
+        r#"{"tag_name": "0.7.2", "assets": []}"#,
+    );
+    server.route("/repos/abiosoft/colima/releases/latest", 500, "oops");
+
+    let mock = Arc::new(MockCommandRunner::new());
+    let forge = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .github_client(GitHubClient::new(&server.url, None));
+
+    let checks = forge.outdated(None).await?;
+    let status = |name: &str| checks.iter().find(|c| c.name == name).unwrap();
+    assert_eq!(status("uv").status, UpdateStatus::Available);
+    assert_eq!(status("uv").latest.as_deref(), Some("0.7.2"));
+    assert_eq!(status("colima").latest, None);
+    assert_eq!(status("colima").status, UpdateStatus::CheckFailed);
+    assert!(status("colima").error.as_deref().unwrap().contains("500"));
+    assert!(mock.calls().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_outdated_exits_distinctly_when_a_check_fails() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+
+    let mut facts = Facts::default();
+    facts.tools.insert(
+        "colima".to_string(),
+        ToolFact {
+            installed_at: Utc::now(),
+            installer: "github".to_string(),
+            version: Some("0.8.0".to_string()),
+            executables: None,
+            provenance: None,
+            pin: None,
+        },
+    );
+    facts.save_to(&paths).await?;
+
+    let server = MockServer::start();
+    server.route("/repos/abiosoft/colima/releases/latest", 500, "oops");
+
+    let outdated = |json: bool| {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_forge"));
+        command
+            .arg("outdated")
+            .env("HOME", home.path())
+            .env("FORGE_GITHUB_API_URL", &server.url)
+            .env_remove("GITHUB_TOKEN");
+        if json {
+            command.arg("--json");
+        }
+        command.output().unwrap()
+    };
+
+    // Neither the table nor the JSON passes a failed check off as up to date
+    let output = outdated(false);
+    assert_eq!(output.status.code(), Some(11));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("check failed"), "{}", stdout);
+    assert!(!stdout.contains("up to date"), "{}", stdout);
+
+    let output = outdated(true);
+    assert_eq!(output.status.code(), Some(11));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let colima = &report["tools"][0];
+    assert_eq!(colima["status"], "check_failed");
+    assert_eq!(colima["latest"], serde_json::Value::Null);
+    assert!(colima["error"].as_str().unwrap().contains("500"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_provenance_resolves_executables_on_the_injected_path() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let cargo_bin = home.path().join("cargo-bin");
+    std::fs::create_dir_all(&cargo_bin)?;
+    std::fs::write(cargo_bin.join("rg"), "\x7fELF rg")?;
+    std::fs::set_permissions(
+        cargo_bin.join("rg"),
+        std::os::unix::fs::PermissionsExt::from_mode(0o755),
+    )?;
+    let paths = Paths::new(home.path()).with_search_path(&cargo_bin);
+
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.1.0`",
+        true,
+    );
+
+    let forge = Forge::with_environment(mock, paths.clone()).await?;
+    forge.install("ripgrep", None).await?;
+
+    // Only the injected PATH is searched, never the process's own
+    let facts = Facts::load_from(&paths).await?;
+    let provenance = facts.tools["ripgrep"].provenance.clone().unwrap();
+    let executables: Vec<_> = provenance
+        .executables
+        .iter()
+        .map(|(name, record)| (name.as_str(), record.path.clone()))
+        .collect();
+    assert_eq!(executables, vec![("rg", cargo_bin.join("rg"))]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_switching_installers_removes_the_old_install_last() -> Result<()> {
+    let (_home, paths) = home_with_sd()?;
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("cargo", &["--version"], "", false);
+    mock.expect("apt", &["--version"], "apt 2.7.14 (amd64)", true);
+    mock.expect(
+        "sudo",
+        &["apt", "install", "-y", "sd"],
+        "Setting up sd (1.0.0-1) ...",
+        true,
+    );
+    mock.expect("sudo", &["apt", "remove", "-y", "sd"], "", true);
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    forge.install("sd", None).await?;
+    let recorded = std::fs::read(paths.facts_file())?;
+
+    // The cargo install fails, so apt's copy and the record stay as they were
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect("cargo", &["install", "sd", "--locked"], "", false);
+    assert!(forge.install("sd", Some("cargo")).await.is_err());
+    assert!(!mock.calls().iter().any(|c| c.contains("apt remove")));
+    assert_eq!(std::fs::read(paths.facts_file())?, recorded);
+
+    // Once cargo succeeds, apt's package is removed after it
+    mock.expect(
+        "cargo",
+        &["install", "sd", "--locked"],
+        "Installed package `sd v1.0.0`",
+        true,
+    );
+    forge.install("sd", Some("cargo")).await?;
+    let calls = mock.calls();
+    let position = |call: &str| calls.iter().rposition(|c| c == call).unwrap();
+    assert!(position("cargo install sd --locked") < position("sudo apt remove -y sd"));
+
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["sd"].installer, "cargo");
+    assert_eq!(facts.tools["sd"].version.as_deref(), Some("1.0.0"));
+
+    Ok(())
+}
@@ -0,0 +1,347 @@
+mod support;
+
+use anyhow::Result;
+use forge::github::{
+    DiscoveryResult, GitHubClient, discover_asset, download_and_install, download_matching,
+};
+use support::MockServer;
+
+fn release_json(server: &MockServer, tag: &str, assets: &[&str]) -> String {
+    let assets: Vec<String> = assets
+        .iter()
+        .map(|name| {
+            format!(
+                r#"{{"name": "{}", "browser_download_url": "{}/download/{}"}}"#,
+                name, server.url, name
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"tag_name": "{}", "assets": [{}]}}"#,
+        tag,
+        assets.join(", ")
+    )
+}
+
+#[test]
+fn test_latest_release_sends_token() -> Result<()> {
+    let server = MockServer::start();
+    server.route(
+        "/repos/astral-sh/uv/releases/latest",
+        200,
+        release_json(&server, "0.7.2", &["uv-x86_64-unknown-linux-gnu.tar.gz"]),
+    );
+
+    let client = GitHubClient::new(&server.url, Some("secret".to_string()));
+    let release = client.latest_release("astral-sh/uv")?;
+
+    assert_eq!(release.tag_name, "0.7.2");
+    assert_eq!(release.assets.len(), 1);
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].headers.get("authorization").map(String::as_str),
+        Some("Bearer secret")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_latest_release_without_token() -> Result<()> {
+    let server = MockServer::start();
+    server.route(
+        "/repos/sharkdp/fd/releases/latest",
+        200,
+        release_json(&server, "v10.2.0", &[]),
+    );
+
+    let client = GitHubClient::new(&server.url, None);
+    client.latest_release("sharkdp/fd")?;
+
+    assert!(!server.requests()[0].headers.contains_key("authorization"));
+
+    // Unknown repos surface as errors
+    assert!(client.latest_release("nobody/nothing").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_discover_asset_scores_release_assets() -> Result<()> {
+    let server = MockServer::start();
+    server.route(
+        "/repos/BurntSushi/ripgrep/releases/latest",
+        200,
+        release_json(
+            &server,
+            "14.1.1",
+            &[
+                "ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz",
+                "ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz.sha256",
+                "ripgrep-14.1.1-aarch64-apple-darwin.tar.gz",
+                "ripgrep_14.1.1-1_amd64.deb",
+            ],
+        ),
+    );
+
+    let client = GitHubClient::new(&server.url, None);
+    let discovery = discover_asset("BurntSushi/ripgrep", "linux", "x86_64", None, &client)?;
+
+    assert_eq!(
+        discovery.asset_name,
+        "ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz"
+    );
+    assert_eq!(discovery.version, "14.1.1");
+    assert_eq!(
+        discovery.download_url,
+        format!(
+            "{}/download/ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz",
+            server.url
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_release_by_version_tries_v_prefix() -> Result<()> {
+    let server = MockServer::start();
+    server.route(
+        "/repos/example/tool/releases/tags/v1.2.3",
+        200,
+        release_json(
+            &server,
+            "v1.2.3",
+            &["tool-1.2.3-x86_64-unknown-linux-musl.tar.gz"],
+        ),
+    );
+
+    let client = GitHubClient::new(&server.url, None);
+    let discovery = discover_asset("example/tool", "linux", "x86_64", Some("1.2.3"), &client)?;
+
+    assert_eq!(discovery.tag, "v1.2.3");
+    assert_eq!(discovery.version, "1.2.3");
+    let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(
+        paths,
+        [
+            "/repos/example/tool/releases/tags/1.2.3",
+            "/repos/example/tool/releases/tags/v1.2.3",
+        ]
+    );
+
+    // A version with no release is reported, not swapped for the latest
+    let err = client.release("example/tool", Some("9.9.9")).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("No release of example/tool is tagged 9.9.9")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_download_matching_installs_assets() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let server = MockServer::start();
+    server.route(
+        "/repos/example/tool/releases/latest",
+        200,
+        release_json(
+            &server,
+            "v1.2.3",
+            &["tool-linux-x86_64", "tool-darwin-arm64"],
+        ),
+    );
+    server.route("/download/tool-linux-x86_64", 200, "#!/bin/sh\necho tool\n");
+
+    let bin_dir = tempfile::tempdir()?;
+    let client = GitHubClient::new(&server.url, None);
+    let result = download_matching(
+        "example/tool",
+        "tool-linux-*",
+        None,
+        None,
+        bin_dir.path(),
+        &client,
+    )?;
+
+    assert_eq!(result.version, "1.2.3");
+    assert_eq!(result.executables, vec!["tool-linux-x86_64".to_string()]);
+
+    let installed = bin_dir.path().join("tool-linux-x86_64");
+    assert_eq!(
+        std::fs::read_to_string(&installed)?,
+        "#!/bin/sh\necho tool\n"
+    );
+    assert_ne!(
+        std::fs::metadata(&installed)?.permissions().mode() & 0o111,
+        0
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_download_and_install_archive() -> Result<()> {
+    use std::io::Write;
+
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, data) in [
+        ("fd-v10.2.0/fd", &b"\x7fELF fake binary"[..]),
+        ("fd-v10.2.0/fd.1", &b".TH FD 1"[..]),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, path, data)?;
+    }
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&builder.into_inner()?)?;
+
+    let server = MockServer::start();
+    server.route("/download/fd.tar.gz", 200, encoder.finish()?);
+
+    let bin_dir = tempfile::tempdir()?;
+    let client = GitHubClient::new(&server.url, None);
+    let discovery = DiscoveryResult {
+        download_url: format!("{}/download/fd.tar.gz", server.url),
+        version: "10.2.0".to_string(),
+        tag: "v10.2.0".to_string(),
+        asset_name: "fd.tar.gz".to_string(),
+        checksum_url: None,
+    };
+    let result = download_and_install(
+        &discovery,
+        "fd",
+        &["fd".to_string()],
+        None,
+        bin_dir.path(),
+        &client,
+    )?;
+
+    assert_eq!(result.executables, vec!["fd".to_string()]);
+    assert!(bin_dir.path().join("fd").exists());
+    assert!(!bin_dir.path().join("fd.1").exists());
+
+    Ok(())
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(data))
+}
+
+#[test]
+fn test_published_checksum_is_verified() -> Result<()> {
+    let binary = b"#!/bin/sh\necho tool\n";
+    let server = MockServer::start();
+    server.route(
+        "/repos/example/tool/releases/latest",
+        200,
+        release_json(
+            &server,
+            "v1.0.0",
+            &["tool-linux-x86_64", "SHA256SUMS", "tool-darwin-arm64"],
+        ),
+    );
+    server.route("/download/tool-linux-x86_64", 200, &binary[..]);
+    server.route(
+        "/download/SHA256SUMS",
+        200,
+        format!(
+            "{}  tool-darwin-arm64\n{} *tool-linux-x86_64\n",
+            sha256_hex(b"other"),
+            sha256_hex(binary)
+        ),
+    );
+
+    let client = GitHubClient::new(&server.url, None);
+    let discovery = discover_asset("example/tool", "linux", "x86_64", None, &client)?;
+    assert_eq!(
+        discovery.checksum_url,
+        Some(format!("{}/download/SHA256SUMS", server.url))
+    );
+
+    let bin_dir = tempfile::tempdir()?;
+    download_and_install(&discovery, "tool", &[], None, bin_dir.path(), &client)?;
+    assert!(bin_dir.path().join("tool").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_checksum_mismatch_refuses_install() -> Result<()> {
+    let server = MockServer::start();
+    server.route(
+        "/repos/example/tool/releases/latest",
+        200,
+        release_json(
+            &server,
+            "v1.0.0",
+            &["tool-linux-x86_64", "tool-linux-x86_64.sha256"],
+        ),
+    );
+    server.route("/download/tool-linux-x86_64", 200, "tampered");
+    server.route(
+        "/download/tool-linux-x86_64.sha256",
+        200,
+        sha256_hex(b"original"),
+    );
+
+    let client = GitHubClient::new(&server.url, None);
+    let discovery = discover_asset("example/tool", "linux", "x86_64", None, &client)?;
+
+    let bin_dir = tempfile::tempdir()?;
+    let err =
+        download_and_install(&discovery, "tool", &[], None, bin_dir.path(), &client).unwrap_err();
+
+    assert!(err.to_string().contains("Checksum mismatch"));
+    assert!(!bin_dir.path().join("tool").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_pinned_digest_is_enforced() -> Result<()> {
+    let server = MockServer::start();
+    server.route(
+        "/repos/example/tool/releases/latest",
+        200,
+        release_json(&server, "v1.0.0", &["tool-linux-x86_64"]),
+    );
+    server.route("/download/tool-linux-x86_64", 200, "payload");
+
+    let client = GitHubClient::new(&server.url, None);
+    let bin_dir = tempfile::tempdir()?;
+
+    let wrong = sha256_hex(b"something else");
+    assert!(
+        download_matching(
+            "example/tool",
+            "tool-linux-*",
+            None,
+            Some(&wrong),
+            bin_dir.path(),
+            &client
+        )
+        .is_err()
+    );
+    assert!(!bin_dir.path().join("tool-linux-x86_64").exists());
+
+    let right = sha256_hex(b"payload");
+    download_matching(
+        "example/tool",
+        "tool-linux-*",
+        None,
+        Some(&right),
+        bin_dir.path(),
+        &client,
+    )?;
+    assert!(bin_dir.path().join("tool-linux-x86_64").exists());
+
+    Ok(())
+}
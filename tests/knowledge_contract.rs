@@ -1,5 +1,5 @@
 use anyhow::Result;
-use forge::backend::{execute_install_with_runner, expand_template};
+use forge::backend::{ensure_version_supported, execute_install_with_runner, expand_template};
 use forge::command::mock::MockCommandRunner;
 use forge::knowledge::Knowledge;
 use forge::platform::Platform;
@@ -67,6 +67,13 @@ async fn test_brew_installer_contract() -> Result<()> {
 
     assert_eq!(result.version, "0.24.0");
 
+    // A versioned formula is a different package, so brew refuses versions
+    let err = ensure_version_supported("brew", installer, "bat", Some("0.23.0")).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("cannot install a specific version")
+    );
+
     Ok(())
 }
 
@@ -145,6 +152,7 @@ fn test_template_expansion() {
         repo: Some("BurntSushi/ripgrep".to_string()),
         pattern: Some("*linux*".to_string()),
         url: None,
+        sha256: None,
         linux: None,
         macos: None,
         windows: None,
@@ -0,0 +1,338 @@
+mod support;
+
+use anyhow::Result;
+use chrono::Utc;
+use forge::command::mock::MockCommandRunner;
+use forge::facts::{Facts, ToolFact};
+use forge::forge::Forge;
+use forge::github::GitHubClient;
+use forge::lock::{LOCK_FILE, Lockfile};
+use forge::paths::Paths;
+use forge::platform::Platform;
+use forge::project::{PROJECT_FILE, ProjectManifest};
+use std::io::Write;
+use std::sync::Arc;
+use support::MockServer;
+
+#[test]
+fn test_manifest_parsing_and_discovery() -> Result<()> {
+    let root = tempfile::tempdir()?;
+    let nested = root.path().join("src").join("module");
+    std::fs::create_dir_all(&nested)?;
+
+    let manifest_path = root.path().join(PROJECT_FILE);
+    std::fs::write(
+        &manifest_path,
+        r#"
+tools = ["ripgrep", "uv"]
+
+[installers]
+uv = "github"
+"#,
+    )?;
+
+    assert_eq!(ProjectManifest::find(&nested), Some(manifest_path.clone()));
+
+    let manifest = ProjectManifest::load(&manifest_path)?;
+    assert_eq!(manifest.tools, vec!["ripgrep", "uv"]);
+    assert_eq!(manifest.installer_for("uv"), Some("github"));
+    assert_eq!(manifest.installer_for("ripgrep"), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_manifest_rejects_installer_for_unlisted_tool() -> Result<()> {
+    let root = tempfile::tempdir()?;
+    let manifest_path = root.path().join(PROJECT_FILE);
+    std::fs::write(
+        &manifest_path,
+        "tools = [\"ripgrep\"]\n\n[installers]\nuv = \"github\"\n",
+    )?;
+
+    let err = ProjectManifest::load(&manifest_path).unwrap_err();
+    assert!(err.to_string().contains("not in its tools list"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_installs_missing_tools() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let project = tempfile::tempdir()?;
+    let manifest_path = project.path().join(PROJECT_FILE);
+    std::fs::write(
+        &manifest_path,
+        "tools = [\"fd\", \"ripgrep\"]\n\n[installers]\nripgrep = \"cargo\"\n",
+    )?;
+    let manifest = manifest_path.to_str().unwrap();
+
+    // fd is already installed
+    let mut facts = Facts::default();
+    facts.tools.insert(
+        "fd".to_string(),
+        ToolFact {
+            installed_at: Utc::now(),
+            installer: "cargo".to_string(),
+            version: Some("10.2.0".to_string()),
+            executables: None,
+            provenance: None,
+            pin: None,
+        },
+    );
+    facts.save_to(&paths).await?;
+
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.1.0`",
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+
+    let err = forge.apply(Some(manifest), true, false).await.unwrap_err();
+    assert!(err.to_string().contains("1 of 2 project tools are missing"));
+    assert!(mock.calls().is_empty());
+
+    forge.apply(Some(manifest), false, false).await?;
+    assert!(
+        Facts::load_from(&paths)
+            .await?
+            .tools
+            .contains_key("ripgrep")
+    );
+    assert!(
+        !mock
+            .calls()
+            .iter()
+            .any(|c| c.contains("install fd") || c.contains("fd-find"))
+    );
+
+    forge.apply(Some(manifest), true, false).await?;
+
+    // Both tools are locked for this platform
+    let triple = Platform::detect()?.target_triple();
+    let lockfile = Lockfile::load(&project.path().join(LOCK_FILE))?;
+    assert_eq!(
+        lockfile.get("ripgrep", &triple).map(|l| l.version.as_str()),
+        Some("14.1.0")
+    );
+    assert_eq!(
+        lockfile.get("fd", &triple).map(|l| l.installer.as_str()),
+        Some("cargo")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_rejects_unknown_tools() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let project = tempfile::tempdir()?;
+    let manifest_path = project.path().join(PROJECT_FILE);
+    std::fs::write(&manifest_path, "tools = [\"not-a-real-tool\"]\n")?;
+
+    let forge =
+        Forge::with_environment(Arc::new(MockCommandRunner::new()), Paths::new(home.path()))
+            .await?;
+    let err = forge
+        .apply(manifest_path.to_str(), false, false)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not-a-real-tool"));
+
+    Ok(())
+}
+
+fn uv_archive(version: &str) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for name in ["uv", "uvx"] {
+        let data = format!("\x7fELF {} {}", name, version);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, name, data.as_bytes())?;
+    }
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&builder.into_inner()?)?;
+    Ok(encoder.finish()?)
+}
+
+fn publish_uv(server: &MockServer, tag: &str) -> Result<()> {
+    let asset = format!("uv-{}-x86_64-unknown-linux-gnu.tar.gz", tag);
+    server.route(
+        "/repos/astral-sh/uv/releases/latest",
+        200,
+        format!(
+            r#"{{"tag_name": "{tag}", "assets": [{{"name": "{asset}", "browser_download_url": "{url}/download/{asset}"}}]}}"#,
+            tag = tag,
+            asset = asset,
+            url = server.url
+        ),
+    );
+    server.route(&format!("/download/{}", asset), 200, uv_archive(tag)?);
+    Ok(())
+}
+
+async fn forge_in(home: &std::path::Path, server: &MockServer) -> Result<Forge> {
+    Ok(
+        Forge::with_environment(Arc::new(MockCommandRunner::new()), Paths::new(home))
+            .await?
+            .github_client(GitHubClient::new(&server.url, None)),
+    )
+}
+
+#[tokio::test]
+async fn test_locked_apply_installs_recorded_assets() -> Result<()> {
+    let server = MockServer::start();
+    publish_uv(&server, "0.7.2")?;
+
+    let project = tempfile::tempdir()?;
+    let manifest_path = project.path().join(PROJECT_FILE);
+    std::fs::write(
+        &manifest_path,
+        "tools = [\"uv\"]\n\n[installers]\nuv = \"github\"\n",
+    )?;
+    let manifest = manifest_path.to_str().unwrap();
+
+    // First developer resolves and locks 0.7.2
+    let first = tempfile::tempdir()?;
+    forge_in(first.path(), &server)
+        .await?
+        .apply(Some(manifest), false, false)
+        .await?;
+
+    let triple = Platform::detect()?.target_triple();
+    let entry = Lockfile::load(&project.path().join(LOCK_FILE))?
+        .get("uv", &triple)
+        .cloned()
+        .expect("uv should be locked");
+    assert_eq!(entry.installer, "github");
+    assert_eq!(entry.tag.as_deref(), Some("0.7.2"));
+    assert_eq!(entry.assets.len(), 1);
+    assert!(entry.assets[0].sha256.is_some());
+
+    // A week later the latest release has moved on
+    publish_uv(&server, "0.8.0")?;
+
+    let second = tempfile::tempdir()?;
+    let forge = forge_in(second.path(), &server).await?;
+    forge.apply(Some(manifest), false, true).await?;
+    forge.apply(Some(manifest), true, true).await?;
+
+    let second_paths = Paths::new(second.path());
+    let fact = Facts::load_from(&second_paths).await?.tools["uv"].clone();
+    assert_eq!(fact.version.as_deref(), Some("0.7.2"));
+    assert_eq!(
+        std::fs::read_to_string(second_paths.bin_dir().join("uv"))?,
+        "\x7fELF uv 0.7.2"
+    );
+
+    // An unlocked install elsewhere picks up 0.8.0 and no longer matches
+    let third = tempfile::tempdir()?;
+    let forge = forge_in(third.path(), &server).await?;
+    forge.install("uv", Some("github")).await?;
+    let err = forge.apply(Some(manifest), true, true).await.unwrap_err();
+    assert!(err.to_string().contains("do not match forge.lock"));
+
+    forge.apply(Some(manifest), false, true).await?;
+    let fact = Facts::load_from(&Paths::new(third.path())).await?.tools["uv"].clone();
+    assert_eq!(fact.version.as_deref(), Some("0.7.2"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_locked_apply_refuses_changed_asset() -> Result<()> {
+    let server = MockServer::start();
+    publish_uv(&server, "0.7.2")?;
+
+    let project = tempfile::tempdir()?;
+    let manifest_path = project.path().join(PROJECT_FILE);
+    std::fs::write(
+        &manifest_path,
+        "tools = [\"uv\"]\n\n[installers]\nuv = \"github\"\n",
+    )?;
+    let manifest = manifest_path.to_str().unwrap();
+
+    let first = tempfile::tempdir()?;
+    forge_in(first.path(), &server)
+        .await?
+        .apply(Some(manifest), false, false)
+        .await?;
+
+    // The release asset is swapped out under the same name
+    server.route(
+        "/download/uv-0.7.2-x86_64-unknown-linux-gnu.tar.gz",
+        200,
+        uv_archive("tampered")?,
+    );
+
+    let second = tempfile::tempdir()?;
+    let err = forge_in(second.path(), &server)
+        .await?
+        .apply(Some(manifest), false, true)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Failed to install uv"));
+    assert!(!Paths::new(second.path()).bin_dir().join("uv").exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_locked_apply_requires_lock_entries() -> Result<()> {
+    let server = MockServer::start();
+    let project = tempfile::tempdir()?;
+    let manifest_path = project.path().join(PROJECT_FILE);
+    std::fs::write(&manifest_path, "tools = [\"uv\"]\n")?;
+
+    let home = tempfile::tempdir()?;
+    let err = forge_in(home.path(), &server)
+        .await?
+        .apply(manifest_path.to_str(), false, true)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("has no"));
+    assert!(err.to_string().contains("entry for uv"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_locked_apply_refuses_versions_the_installer_cannot_request() -> Result<()> {
+    let project = tempfile::tempdir()?;
+    let manifest_path = project.path().join(PROJECT_FILE);
+    std::fs::write(&manifest_path, "tools = [\"k3s\"]\n")?;
+
+    let triple = Platform::detect()?.target_triple();
+    std::fs::write(
+        project.path().join(LOCK_FILE),
+        format!(
+            "[tools.k3s.{}]\ninstaller = \"script\"\nversion = \"1.31.0\"\n",
+            triple
+        ),
+    )?;
+
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+    let err = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .apply(manifest_path.to_str(), false, true)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("cannot request"), "{}", err);
+    assert!(err.to_string().contains("k3s (1.31.0)"), "{}", err);
+
+    // Nothing ran and nothing was recorded
+    assert!(mock.calls().is_empty());
+    assert!(!paths.facts_file().exists());
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use forge::command::mock::MockCommandRunner;
+use forge::forge::Forge;
+use forge::paths::Paths;
+use forge::report::{UpdateList, to_json};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+fn document<T: serde::Serialize>(body: &T) -> Result<Value> {
+    Ok(serde_json::from_str(&to_json(body)?)?)
+}
+
+#[tokio::test]
+async fn test_json_reports_keep_their_schema() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.0.3`",
+        true,
+    );
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/ripgrep"],
+        r#"{"crate": {"max_version": "14.1.0"}}"#,
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    forge.install("ripgrep", None).await?;
+    forge.pin("ripgrep", Some("14")).await?;
+
+    let list = document(&forge.installed_tools().await?)?;
+    assert_eq!(list["schema_version"], 1);
+    let ripgrep = &list["tools"][0];
+    assert_eq!(ripgrep["name"], "ripgrep");
+    assert_eq!(ripgrep["installer"], "cargo");
+    assert_eq!(ripgrep["version"], "14.0.3");
+    assert_eq!(ripgrep["local"], false);
+    assert_eq!(ripgrep["pin"], json!({"range": "14"}));
+    assert!(ripgrep["description"].as_str().unwrap().contains("search"));
+    assert!(ripgrep["installed_at"].is_string());
+    assert!(ripgrep["executables"].is_object());
+
+    let info = document(&forge.tool_info("ripgrep").await?)?;
+    assert_eq!(info["provides"], json!(["rg"]));
+    assert_eq!(info["installers"], json!(["brew", "cargo", "github"]));
+    assert_eq!(info["installed"]["version"], "14.0.3");
+
+    let tools = forge.update_checks(None).await?;
+    let updates = document(&UpdateList { tools })?;
+    assert_eq!(
+        updates["tools"],
+        json!([{
+            "name": "ripgrep",
+            "installer": "cargo",
+            "current": "14.0.3",
+            "latest": "14.1.0",
+            "status": "available",
+        }])
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_plan_json_describes_each_candidate() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+    mock.expect("cargo", &["--version"], "", false);
+
+    let forge = Forge::with_environment(mock, paths).await?;
+    let plan = document(&forge.install_plan("ripgrep@14.1.0").await?)?;
+
+    assert_eq!(plan["name"], "ripgrep");
+    assert_eq!(plan["version"], "14.1.0");
+    assert_eq!(plan["installed"], Value::Null);
+    let candidates = plan["candidates"].as_array().unwrap();
+    let status = |installer: &str| {
+        candidates
+            .iter()
+            .find(|c| c["installer"] == installer)
+            .map(|c| c["status"].clone())
+    };
+    if plan["os"] == "linux" {
+        assert_eq!(status("script"), Some(json!("not_declared")));
+        assert_eq!(status("cargo"), Some(json!("unavailable")));
+        assert_eq!(status("github"), Some(json!("selected")));
+        assert_eq!(status("brew"), Some(json!("not_used")));
+        assert_eq!(plan["selected"], "github");
+        assert_eq!(
+            plan["action"],
+            json!({
+                "type": "github_release",
+                "repo": "BurntSushi/ripgrep",
+                "pattern": null,
+                "version": "14.1.0",
+            })
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+mod support;
+
+use anyhow::Result;
+use forge::command::mock::MockCommandRunner;
+use forge::facts::Facts;
+use forge::forge::Forge;
+use forge::github::GitHubClient;
+use forge::history::History;
+use forge::paths::Paths;
+use std::sync::Arc;
+use support::MockServer;
+
+#[tokio::test]
+async fn test_rollback_restores_kept_binaries() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    let asset = "uv-x86_64-unknown-linux-gnu";
+    let server = MockServer::start();
+    let publish = |tag: &str| {
+        server.route(
+            "/repos/astral-sh/uv/releases/latest",
+            200,
+            format!(
+                r#"{{"tag_name": "{tag}", "assets": [{{"name": "{asset}", "browser_download_url": "{url}/download/{tag}/{asset}"}}]}}"#,
+                url = server.url
+            ),
+        );
+        server.route(
+            &format!("/download/{}/{}", tag, asset),
+            200,
+            format!("\x7fELF uv {}", tag),
+        );
+    };
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .github_client(GitHubClient::new(&server.url, None));
+
+    publish("0.7.2");
+    forge.install("uv", Some("github")).await?;
+    publish("0.8.0");
+    forge.update(Some("uv"), true).await?;
+
+    let uv = paths.bin_dir().join("uv");
+    assert_eq!(std::fs::read_to_string(&uv)?, "\x7fELF uv 0.8.0");
+    let history = History::load(&paths, "uv")?;
+    assert_eq!(history.entries.len(), 1);
+    assert_eq!(history.entries[0].fact.version.as_deref(), Some("0.7.2"));
+
+    forge.rollback("uv").await?;
+
+    assert_eq!(std::fs::read_to_string(&uv)?, "\x7fELF uv 0.7.2");
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["uv"].version.as_deref(), Some("0.7.2"));
+    assert!(History::load(&paths, "uv")?.entries.is_empty());
+    assert!(!paths.history_dir("uv").exists());
+
+    // Nothing further back was kept
+    assert!(forge.rollback("uv").await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rollback_reinstalls_package_manager_version() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    mock.expect("cargo", &["--version"], "cargo 1.88.0", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked"],
+        "Installed package `ripgrep v14.0.3`",
+        true,
+    );
+    mock.expect(
+        "curl",
+        &["-s", "https://crates.io/api/v1/crates/ripgrep"],
+        r#"{"crate": {"max_version": "14.1.0"}}"#,
+        true,
+    );
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked", "--force"],
+        "Replaced package `ripgrep v14.0.3` with `ripgrep v14.1.0` (executable `rg`)",
+        true,
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone()).await?;
+    forge.install("ripgrep", None).await?;
+    forge.update(Some("ripgrep"), true).await?;
+
+    let history = History::load(&paths, "ripgrep")?;
+    assert_eq!(history.entries.len(), 1);
+    assert!(history.entries[0].saved.is_empty());
+
+    mock.expect("cargo", &["uninstall", "ripgrep"], "", true);
+    mock.expect(
+        "cargo",
+        &["install", "ripgrep", "--locked", "--version", "14.0.3"],
+        "Installed package `ripgrep v14.0.3`",
+        true,
+    );
+    forge.rollback("ripgrep").await?;
+
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["ripgrep"].version.as_deref(), Some("14.0.3"));
+    assert!(History::load(&paths, "ripgrep")?.entries.is_empty());
+
+    Ok(())
+}
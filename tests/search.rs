@@ -0,0 +1,68 @@
+use anyhow::Result;
+use chrono::Utc;
+use forge::command::mock::MockCommandRunner;
+use forge::facts::{Facts, ToolFact};
+use forge::forge::Forge;
+use forge::paths::Paths;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_search_ranks_names_before_other_fields() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let forge = Forge::with_environment(Arc::new(MockCommandRunner::new()), paths).await?;
+
+    let names = |results: Vec<forge::search::SearchResult>| {
+        results.into_iter().map(|r| r.name).collect::<Vec<_>>()
+    };
+
+    // An exact name beats a tool that only provides the executable
+    let kubectl = names(forge.search_tools("kubectl").await?);
+    assert_eq!(kubectl, vec!["kubectl", "k3s"]);
+
+    // Executables and package names are searched too
+    assert_eq!(names(forge.search_tools("rg").await?)[0], "ripgrep");
+    assert_eq!(names(forge.search_tools("git-delta").await?), vec!["delta"]);
+
+    // Every term has to match, case-insensitively
+    assert_eq!(
+        names(forge.search_tools("PYTHON installer").await?),
+        vec!["uv"]
+    );
+    assert!(forge.search_tools("no-such-tool").await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_marks_installed_and_installable() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+
+    let mut facts = Facts::default();
+    facts.tools.insert(
+        "fd".to_string(),
+        ToolFact {
+            installed_at: Utc::now(),
+            installer: "cargo".to_string(),
+            version: Some("10.2.0".to_string()),
+            executables: None,
+            provenance: None,
+            pin: None,
+        },
+    );
+    facts.save_to(&paths).await?;
+
+    let forge = Forge::with_environment(Arc::new(MockCommandRunner::new()), paths).await?;
+
+    let fd = &forge.search_tools("fd").await?[0];
+    assert_eq!(fd.name, "fd");
+    assert_eq!(fd.installed.as_deref(), Some("10.2.0"));
+    assert!(fd.installable);
+
+    let homebrew = &forge.search_tools("homebrew").await?[0];
+    assert_eq!(homebrew.installed, None);
+    assert_eq!(homebrew.installable, cfg!(target_os = "macos"));
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Minimal HTTP server for exercising the GitHub client without network access
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+type Routes = Arc<Mutex<HashMap<String, (u16, Vec<u8>)>>>;
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub path: String,
+    pub headers: HashMap<String, String>,
+}
+
+pub struct MockServer {
+    pub url: String,
+    routes: Routes,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let routes: Routes = Arc::default();
+        let requests: Arc<Mutex<Vec<RecordedRequest>>> = Arc::default();
+
+        let thread_routes = routes.clone();
+        let thread_requests = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+                let path = request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or("/")
+                    .to_string();
+
+                let mut headers = HashMap::new();
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.trim_end().split_once(':') {
+                        headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+                    }
+                }
+
+                thread_requests.lock().unwrap().push(RecordedRequest {
+                    path: path.clone(),
+                    headers,
+                });
+
+                let (status, body) = thread_routes
+                    .lock()
+                    .unwrap()
+                    .get(&path)
+                    .cloned()
+                    .unwrap_or((404, b"{\"message\": \"Not Found\"}".to_vec()));
+
+                let head = format!(
+                    "HTTP/1.1 {} MOCK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(&body);
+            }
+        });
+
+        Self {
+            url,
+            routes,
+            requests,
+        }
+    }
+
+    pub fn route(&self, path: &str, status: u16, body: impl Into<Vec<u8>>) {
+        self.routes
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), (status, body.into()));
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::Utc;
+use forge::facts::ToolFact;
+use forge::updates::{UpdateCheck, UpdateStatus};
+use forge::vercmp::{Scheme, compare, is_newer};
+use std::cmp::Ordering;
+
+fn assert_ascending(versions: &[&str], scheme: Scheme) {
+    for pair in versions.windows(2) {
+        assert_eq!(
+            compare(pair[0], pair[1], scheme),
+            Ordering::Less,
+            "{} should sort before {} ({:?})",
+            pair[0],
+            pair[1],
+            scheme
+        );
+        assert_eq!(compare(pair[1], pair[0], scheme), Ordering::Greater);
+    }
+}
+
+#[test]
+fn test_semver_ordering() {
+    // The precedence example from the semver spec
+    assert_ascending(
+        &[
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.2.0",
+            "1.10.0",
+            "2.0.0",
+        ],
+        Scheme::Semver,
+    );
+
+    assert_eq!(compare("v1.2.3", "1.2.3", Scheme::Semver), Ordering::Equal);
+    assert_eq!(compare("1.2", "1.2.0", Scheme::Semver), Ordering::Equal);
+    assert_eq!(
+        compare("1.2.3+build.5", "1.2.3", Scheme::Semver),
+        Ordering::Equal
+    );
+}
+
+#[test]
+fn test_debian_ordering() {
+    assert_ascending(
+        &[
+            "1.0~rc1-1",
+            "1.0-1",
+            "1.0-1ubuntu1",
+            "1.0-2",
+            "1.0a-1",
+            "1.0+dfsg-1",
+            "1.2-1",
+            "1.10-1",
+            "1:0.9-1",
+            "2:0.1-1",
+        ],
+        Scheme::Debian,
+    );
+
+    // A missing epoch is epoch 0
+    assert_eq!(compare("0:2.3-1", "2.3-1", Scheme::Debian), Ordering::Equal);
+    assert_eq!(compare("2.3", "2.3-0", Scheme::Debian), Ordering::Equal);
+}
+
+#[test]
+fn test_homebrew_ordering() {
+    assert_ascending(
+        &["0.23.9", "0.24.0_1", "0.24.0_2", "0.24.1"],
+        Scheme::Homebrew,
+    );
+
+    // Without a revision on both sides only the version counts
+    assert_eq!(
+        compare("0.24.0_1", "0.24.0", Scheme::Homebrew),
+        Ordering::Equal
+    );
+    assert!(!is_newer("0.24.0", "0.24.0_1", Scheme::Homebrew));
+    assert!(!is_newer("0.24.0_1", "0.24.0", Scheme::Homebrew));
+
+    // So a brew install recorded without its revision has no phantom update
+    let fact = ToolFact {
+        installed_at: Utc::now(),
+        installer: "brew".to_string(),
+        version: Some("0.24.0".to_string()),
+        executables: None,
+        provenance: None,
+        pin: None,
+    };
+    let check = UpdateCheck::new("bat", &fact, Some("0.24.0_1".to_string()));
+    assert_eq!(check.status, UpdateStatus::UpToDate);
+    let check = UpdateCheck::new("bat", &fact, Some("0.25.0".to_string()));
+    assert_eq!(check.status, UpdateStatus::Available);
+}
+
+#[test]
+fn test_is_newer_never_offers_downgrades() {
+    assert!(is_newer("14.1.0", "14.0.3", Scheme::Semver));
+    assert!(!is_newer("14.0.3", "14.1.0", Scheme::Semver));
+    assert!(!is_newer("14.1.0", "v14.1.0", Scheme::Semver));
+    assert!(!is_newer("2.0.0-rc.1", "2.0.0", Scheme::Semver));
+
+    assert!(!is_newer("2.3-1", "1:2.3-1", Scheme::Debian));
+    assert!(is_newer("1:2.3-2", "1:2.3-1", Scheme::Debian));
+
+    assert_eq!(Scheme::for_installer("apt"), Scheme::Debian);
+    assert_eq!(Scheme::for_installer("brew"), Scheme::Homebrew);
+    assert_eq!(Scheme::for_installer("cargo"), Scheme::Semver);
+}
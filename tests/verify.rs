@@ -0,0 +1,196 @@
+mod support;
+
+use anyhow::Result;
+use chrono::Utc;
+use forge::command::mock::MockCommandRunner;
+use forge::facts::{ExecutableRecord, Facts, Provenance, ToolFact};
+use forge::forge::Forge;
+use forge::github::GitHubClient;
+use forge::paths::Paths;
+use forge::verify::{Integrity, check_executables};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use support::MockServer;
+
+fn fact_with(executables: &[(&str, &Path)]) -> Result<ToolFact> {
+    let mut provenance = Provenance::default();
+    for (name, path) in executables {
+        provenance.executables.insert(
+            name.to_string(),
+            ExecutableRecord {
+                path: path.to_path_buf(),
+                sha256: forge::checksum::sha256_file(path)?,
+            },
+        );
+    }
+    Ok(ToolFact {
+        installed_at: Utc::now(),
+        installer: "github".to_string(),
+        version: Some("1.0.0".to_string()),
+        executables: Some(executables.iter().map(|(n, _)| n.to_string()).collect()),
+        provenance: Some(provenance),
+        pin: None,
+    })
+}
+
+#[test]
+fn test_check_executables_classifies_drift() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let bin = paths.bin_dir();
+    std::fs::create_dir_all(bin)?;
+
+    for name in ["intact", "modified", "deleted", "replaced", "other"] {
+        std::fs::write(bin.join(name), format!("binary {}", name))?;
+    }
+    // Outside the bin dir, owned by a package manager
+    let elsewhere = home.path().join("cargo-bin");
+    std::fs::write(&elsewhere, "cargo binary")?;
+
+    let mut facts = Facts::default();
+    facts.tools.insert(
+        "alpha".to_string(),
+        fact_with(&[
+            ("intact", &bin.join("intact")),
+            ("modified", &bin.join("modified")),
+            ("deleted", &bin.join("deleted")),
+            ("replaced", &bin.join("replaced")),
+        ])?,
+    );
+    facts.tools.insert(
+        "beta".to_string(),
+        fact_with(&[("other", &bin.join("other"))])?,
+    );
+    facts.tools.insert(
+        "gamma".to_string(),
+        fact_with(&[("cargo-bin", &elsewhere)])?,
+    );
+
+    std::fs::write(bin.join("modified"), "tampered")?;
+    std::fs::remove_file(bin.join("deleted"))?;
+    std::fs::copy(bin.join("other"), bin.join("replaced"))?;
+    std::fs::write(&elsewhere, "upgraded by cargo")?;
+
+    let checks = check_executables(&facts, bin);
+    let status = |exe: &str| {
+        checks
+            .iter()
+            .find(|c| c.executable == exe)
+            .map(|c| c.integrity.clone())
+    };
+
+    assert_eq!(status("intact"), Some(Integrity::Intact));
+    assert_eq!(status("modified"), Some(Integrity::Modified));
+    assert_eq!(status("deleted"), Some(Integrity::Deleted));
+    assert_eq!(
+        status("replaced"),
+        Some(Integrity::Replaced {
+            by: "beta".to_string()
+        })
+    );
+    assert_eq!(status("other"), Some(Integrity::Intact));
+    assert_eq!(status("cargo-bin"), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_fails_on_drift_and_restores() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    let mut builder = tar::Builder::new(Vec::new());
+    for name in ["uv", "uvx"] {
+        let data = format!("\x7fELF {}", name);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, name, data.as_bytes())?;
+    }
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&builder.into_inner()?)?;
+
+    let asset = "uv-x86_64-unknown-linux-gnu.tar.gz";
+    let server = MockServer::start();
+    let release = format!(
+        r#"{{"tag_name": "0.7.2", "assets": [{{"name": "{asset}", "browser_download_url": "{url}/download/{asset}"}}]}}"#,
+        asset = asset,
+        url = server.url
+    );
+    server.route("/repos/astral-sh/uv/releases/latest", 200, release.clone());
+    // Restoring asks for the recorded version
+    server.route("/repos/astral-sh/uv/releases/tags/0.7.2", 200, release);
+    server.route(&format!("/download/{}", asset), 200, encoder.finish()?);
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .github_client(GitHubClient::new(&server.url, None));
+    forge.install("uv", Some("github")).await?;
+    forge.verify(None, false).await?;
+
+    let uv = paths.bin_dir().join("uv");
+    let original = std::fs::read(&uv)?;
+    std::fs::write(&uv, "tampered")?;
+    std::fs::remove_file(paths.bin_dir().join("uvx"))?;
+
+    let err = forge.verify(None, false).await.unwrap_err();
+    assert!(err.to_string().contains("2 of 2 executables"));
+
+    forge.verify(Some("uv"), true).await?;
+    assert_eq!(std::fs::read(&uv)?, original);
+    assert!(paths.bin_dir().join("uvx").exists());
+    forge.verify(None, false).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_keeps_a_pinned_version() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let paths = Paths::new(home.path());
+    let mock = Arc::new(MockCommandRunner::new());
+
+    let asset = "uv-x86_64-unknown-linux-gnu";
+    let server = MockServer::start();
+    let release = |tag: &str| {
+        server.route(
+            &format!("/download/{}/{}", tag, asset),
+            200,
+            format!("\x7fELF uv {}", tag),
+        );
+        format!(
+            r#"{{"tag_name": "{tag}", "assets": [{{"name": "{asset}", "browser_download_url": "{url}/download/{tag}/{asset}"}}]}}"#,
+            url = server.url
+        )
+    };
+    server.route("/repos/astral-sh/uv/releases/latest", 200, release("0.7.2"));
+    server.route(
+        "/repos/astral-sh/uv/releases/tags/0.7.2",
+        200,
+        release("0.7.2"),
+    );
+
+    let forge = Forge::with_environment(mock.clone(), paths.clone())
+        .await?
+        .github_client(GitHubClient::new(&server.url, None));
+    forge.install("uv", Some("github")).await?;
+    forge.pin("uv", None).await?;
+
+    // A newer release comes out, then the binary is tampered with
+    server.route("/repos/astral-sh/uv/releases/latest", 200, release("0.8.0"));
+    let uv = paths.bin_dir().join("uv");
+    std::fs::write(&uv, "tampered")?;
+
+    forge.verify(Some("uv"), true).await?;
+
+    assert_eq!(std::fs::read_to_string(&uv)?, "\x7fELF uv 0.7.2");
+    let facts = Facts::load_from(&paths).await?;
+    assert_eq!(facts.tools["uv"].version.as_deref(), Some("0.7.2"));
+    assert!(facts.tools["uv"].pin.is_some());
+    forge.verify(None, false).await?;
+
+    Ok(())
+}
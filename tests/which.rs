@@ -0,0 +1,85 @@
+use anyhow::Result;
+use chrono::Utc;
+use forge::command::mock::MockCommandRunner;
+use forge::facts::{ExecutableRecord, Facts, Provenance, ToolFact};
+use forge::forge::Forge;
+use forge::paths::Paths;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::sync::Arc;
+
+fn write_executable(path: &Path) -> Result<()> {
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, "#!/bin/sh\n")?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_which_finds_providers_and_the_binary_that_runs() -> Result<()> {
+    let home = tempfile::tempdir()?;
+    let system_dir = home.path().join("system");
+    let bin_dir = home.path().join("bin");
+    write_executable(&system_dir.join("kubectl"))?;
+    write_executable(&bin_dir.join("kubectl"))?;
+
+    let mut facts = Facts::default();
+    facts.tools.insert(
+        "kubectl".to_string(),
+        ToolFact {
+            installed_at: Utc::now(),
+            installer: "script".to_string(),
+            version: Some("1.31.0".to_string()),
+            executables: None,
+            provenance: Some(Provenance {
+                executables: [(
+                    "kubectl".to_string(),
+                    ExecutableRecord {
+                        path: bin_dir.join("kubectl"),
+                        sha256: String::new(),
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            }),
+            pin: None,
+        },
+    );
+    let base = Paths::new(home.path());
+    facts.save_to(&base).await?;
+
+    let search_path = |dirs: [&Path; 2]| std::env::join_paths(dirs).unwrap();
+    let forge = |paths: Paths| Forge::with_environment(Arc::new(MockCommandRunner::new()), paths);
+
+    // A binary forge did not install shadows the one it did
+    let paths = base
+        .clone()
+        .with_search_path(search_path([&system_dir, &bin_dir]));
+    let location = forge(paths).await?.locate("kubectl").await?;
+    let providers: Vec<&str> = location.providers.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(providers, vec!["kubectl", "k3s"]);
+    assert_eq!(location.providers[0].installed.as_deref(), Some("1.31.0"));
+    assert_eq!(location.providers[0].path, Some(bin_dir.join("kubectl")));
+    assert_eq!(location.providers[1].installed, None);
+    assert_eq!(
+        location.on_path,
+        vec![system_dir.join("kubectl"), bin_dir.join("kubectl")]
+    );
+    assert_eq!(location.owner, None);
+
+    // With forge's directory first, its binary is the one that runs
+    let paths = base.with_search_path(search_path([&bin_dir, &system_dir]));
+    let forge = forge(paths).await?;
+    let location = forge.locate("kubectl").await?;
+    assert_eq!(location.on_path[0], bin_dir.join("kubectl"));
+    assert_eq!(location.owner.as_deref(), Some("kubectl"));
+    forge.which("kubectl").await?;
+
+    // Nothing provides an unknown executable and nothing runs for it
+    let location = forge.locate("no-such-executable").await?;
+    assert!(location.providers.is_empty());
+    assert!(location.on_path.is_empty());
+    forge.which("no-such-executable").await?;
+
+    Ok(())
+}